@@ -0,0 +1,150 @@
+use crate::error::SolveError;
+use crate::runner;
+use crate::solution::AnySolution;
+use std::time::{Duration, Instant};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty());
+        let mut samples = samples.to_vec();
+        samples.sort();
+        Self {
+            min: samples[0],
+            median: percentile(&samples, 50),
+            p95: percentile(&samples, 95)
+        }
+    }
+}
+
+impl std::fmt::Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:.2?} / {:.2?} / {:.2?}", self.min, self.median, self.p95)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Measurement {
+    pub parse: Stats,
+    pub solve: Stats
+}
+
+// Nearest-rank percentile over sorted samples.
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (sorted.len() * percent).div_ceil(100);
+    sorted[rank.max(1) - 1]
+}
+
+pub fn measure(
+    solution: &dyn AnySolution,
+    input: &str,
+    part: u32,
+    warmup: usize,
+    iterations: usize
+) -> Result<Measurement, String> {
+    for _ in 0..warmup {
+        runner::solve(solution, input, part)?;
+    }
+
+    let mut parse_samples = Vec::with_capacity(iterations);
+    let mut solve_samples = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let (parse, solve) = time(solution, input, part)?;
+        parse_samples.push(parse);
+        solve_samples.push(solve);
+    }
+
+    Ok(Measurement {
+        parse: Stats::from_samples(&parse_samples),
+        solve: Stats::from_samples(&solve_samples)
+    })
+}
+
+// How long one run takes to parse then solve. Failures and panics are
+// reported like in runner::solve, so that even without warmup an invalid
+// input isn't timed.
+fn time(solution: &dyn AnySolution, input: &str, part: u32) -> Result<(Duration, Duration), String> {
+    let timer = std::panic::AssertUnwindSafe(|| {
+        let start = Instant::now();
+        let parsed = solution.parse_any(std::hint::black_box(input))?;
+        let parsed_at = Instant::now();
+        let answer = std::hint::black_box(solution.solve_any(parsed.as_ref(), part));
+        let solved_at = Instant::now();
+        answer?;
+        Ok((parsed_at - start, solved_at - parsed_at))
+    });
+
+    match std::panic::catch_unwind(timer) {
+        Ok(result) => result.map_err(|error: SolveError| error.to_string()),
+        Err(payload) => Err(runner::panic_message(payload.as_ref()))
+    }
+}
+
+#[test]
+fn test_stats() {
+    let samples: Vec<Duration> = (1..=20).rev()
+        .map(Duration::from_millis)
+        .collect();
+    assert_eq!(
+        Stats::from_samples(&samples),
+        Stats {
+            min: Duration::from_millis(1),
+            median: Duration::from_millis(10),
+            p95: Duration::from_millis(19)
+        }
+    );
+
+    let single = [Duration::from_millis(3)];
+    assert_eq!(
+        Stats::from_samples(&single),
+        Stats {
+            min: Duration::from_millis(3),
+            median: Duration::from_millis(3),
+            p95: Duration::from_millis(3)
+        }
+    );
+}
+
+#[test]
+fn test_failures() {
+    use crate::error::ParseError;
+    use crate::registry;
+    use crate::solution::{Answer, Solution};
+
+    // Parses anything, then panics in part 2.
+    struct Panicky;
+
+    impl Solution for Panicky {
+        type Parsed = ();
+
+        fn day(&self) -> u32 {
+            1
+        }
+
+        fn parse(&self, _: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part1(&self, _: &()) -> Result<Answer, SolveError> {
+            Ok(0)
+        }
+
+        fn part2(&self, _: &()) -> Result<Answer, SolveError> {
+            panic!("no part 2")
+        }
+    }
+
+    let day6 = registry::find(6).unwrap();
+    assert!(measure(day6, "Time: 7\nDistance: 9", 1, 0, 2).is_ok());
+    assert!(measure(day6, "Time: 7", 1, 0, 2).is_err());
+    assert!(measure(&Panicky, "", 1, 0, 2).is_ok());
+    assert_eq!(measure(&Panicky, "", 2, 0, 2).unwrap_err(), "no part 2");
+    assert_eq!(measure(&Panicky, "", 2, 1, 2).unwrap_err(), "no part 2");
+}
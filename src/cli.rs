@@ -0,0 +1,897 @@
+use adventofcode2023::fetch;
+use adventofcode2023::input::{self, Input};
+use adventofcode2023::manifest;
+use adventofcode2023::report::Format;
+use adventofcode2023::submit;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "Usage:
+  adventofcode2023 run <days> [options] [--manifest <path>] [--record]
+                       [--format <text|json|csv>] [--jobs <n>] [--explain]
+  adventofcode2023 bench <days> [options] [--iterations <n>] [--warmup <n>]
+  adventofcode2023 new-day <day>
+  adventofcode2023 fetch <day> [--base-url <url>] [--session-file <path>]
+                       [--input-dir <dir>]
+  adventofcode2023 submit <day> <part> [--input <path>] [--input-dir <dir>]
+                       [--base-url <url>] [--session-file <path>]
+                       [--history <path>] [--manifest <path>]
+  adventofcode2023 watch <day> [options]
+  adventofcode2023 repl <day> [options]
+  adventofcode2023 gen <day> [--seed <n>] [--size <n>]
+  adventofcode2023 fuzz <days> [--seed <n>] [--iterations <n>]
+
+<days> is one of:
+  all     every available day
+  N       a single day, e.g. 5
+  A..B    an inclusive range of days, e.g. 3..7
+
+Options:
+  --part <1|2>         only run one part
+  --input <path>       read the input from a file, or stdin if <path> is -
+                       (single day only)
+  --input-dir <dir>    read inputs from <dir>/<day>.txt
+                       (default: problem_inputs)
+
+Run options:
+  --manifest <path>    check answers against this manifest
+                       (default: problem_inputs/answers.txt)
+  --record             store the computed answers in the manifest
+  --format <format>    print results as text, json or csv (default: text)
+  --jobs <n>           solve up to <n> parts at the same time (default: 1)
+  --explain            show how each answer was found (text or json only)
+
+Bench options:
+  --iterations <n>     timed runs per part (default: 10)
+  --warmup <n>         untimed runs per part before timing (default: 1)
+
+new-day creates src/day<day>.rs, an empty problem_inputs/<day>.txt and an
+empty example in examples/<day>/, and registers the day as unsolved in
+src/registry.rs. It must be run from the repository root, and <day> must
+follow the last available day.
+
+fetch downloads the input of <day> to <dir>/<day>.txt (default:
+problem_inputs), unless that file already has contents. It authenticates with
+the session cookie from $AOC_SESSION, or else from the session file (default:
+.aoc-session). The default base URL is https://adventofcode.com/2023.
+
+submit solves one part and posts the answer, with the same session and base
+URL as fetch. Every attempt is stored in the history file (default:
+problem_inputs/submissions.txt), and answers that it shows to be wrong are not
+submitted. Correct answers are also recorded in the manifest.
+
+watch reruns the tests, the examples and the input of <day> whenever
+src/day<day>*.rs or its input file changes, and shows how the answers changed.
+It must be run from the repository root.
+
+repl parses the input of <day> and reads commands over it from stdin, such as
+solve, or ones that show parts of the parsed input. help lists them.
+
+gen prints a random input for <day>. The same seed (default: 0) always gives
+the same input, and the size (default: 10) roughly scales it.
+
+fuzz feeds mutated inputs to the parsers and solvers of <days> (default: 10000
+per day), and shows the smallest input it finds that makes one panic. The same
+seed (default: 0) always gives the same inputs.";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Bench(BenchOptions),
+    NewDay(u32),
+    Fetch(FetchOptions),
+    Submit(SubmitOptions),
+    Watch(Selection),
+    Repl(Selection),
+    Generate(GenerateOptions),
+    Fuzz(FuzzOptions)
+}
+
+// Which days and parts to execute, and where their inputs come from.
+#[derive(Debug, PartialEq)]
+pub struct Selection {
+    pub days: Vec<u32>,
+    pub parts: Vec<u32>,
+    pub input: Input
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    pub selection: Selection,
+    pub manifest: PathBuf,
+    pub record: bool,
+    pub format: Format,
+    pub jobs: usize,
+    pub explain: bool
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub selection: Selection,
+    pub iterations: usize,
+    pub warmup: usize
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FetchOptions {
+    pub day: u32,
+    pub base_url: String,
+    pub session_file: PathBuf,
+    pub directory: PathBuf
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SubmitOptions {
+    pub day: u32,
+    pub part: u32,
+    pub input: Input,
+    pub base_url: String,
+    pub session_file: PathBuf,
+    pub history: PathBuf,
+    pub manifest: PathBuf
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GenerateOptions {
+    pub day: u32,
+    pub seed: u64,
+    pub size: usize
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FuzzOptions {
+    pub days: Vec<u32>,
+    pub seed: u64,
+    pub iterations: usize
+}
+
+pub fn parse_args<T: AsRef<str>>(
+    args: &[T],
+    available_days: u32
+) -> Result<Command, String> {
+    let args: Vec<&str> = args.iter().map(|arg| arg.as_ref()).collect();
+
+    match args.first() {
+        Some(&"run") => parse_run(&args[1..], available_days),
+        Some(&"bench") => parse_bench(&args[1..], available_days),
+        Some(&"new-day") => parse_new_day(&args[1..], available_days),
+        Some(&"fetch") => parse_fetch(&args[1..]),
+        Some(&"submit") => parse_submit(&args[1..], available_days),
+        Some(&"watch") => parse_watch(&args[1..], available_days),
+        Some(&"repl") => parse_repl(&args[1..], available_days),
+        Some(&"gen") => parse_generate(&args[1..], available_days),
+        Some(&"fuzz") => parse_fuzz(&args[1..], available_days),
+        Some(command) => Err(format!("Unknown command {:?}", command)),
+        None => Err("Missing command".to_owned())
+    }
+}
+
+fn parse_run(args: &[&str], available_days: u32) -> Result<Command, String> {
+    let mut selection = SelectionBuilder::new(available_days);
+    let mut manifest = PathBuf::from(manifest::DEFAULT_PATH);
+    let mut record = false;
+    let mut format = Format::Text;
+    let mut jobs = 1;
+    let mut explain = false;
+    let mut args = Args::new(args);
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--manifest" => {
+                manifest = args.value(arg)?.into();
+            }
+            "--record" => {
+                record = true;
+            }
+            "--explain" => {
+                explain = true;
+            }
+            "--format" => {
+                format = args.value(arg)?.parse()?;
+            }
+            "--jobs" => {
+                jobs = parse_count(arg, args.value(arg)?)?;
+
+                if jobs == 0 {
+                    return Err("--jobs must be at least 1".to_owned());
+                }
+            }
+            _ => {
+                selection.parse(arg, &mut args)?;
+            }
+        }
+    }
+
+    if explain && format == Format::Csv {
+        return Err("--explain can't be used with --format csv".to_owned());
+    }
+
+    Ok(Command::Run(RunOptions {
+        selection: selection.build()?,
+        manifest,
+        record,
+        format,
+        jobs,
+        explain
+    }))
+}
+
+fn parse_bench(args: &[&str], available_days: u32) -> Result<Command, String> {
+    let mut selection = SelectionBuilder::new(available_days);
+    let mut iterations = 10;
+    let mut warmup = 1;
+    let mut args = Args::new(args);
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--iterations" => {
+                iterations = parse_count(arg, args.value(arg)?)?;
+
+                if iterations == 0 {
+                    return Err("--iterations must be at least 1".to_owned());
+                }
+            }
+            "--warmup" => {
+                warmup = parse_count(arg, args.value(arg)?)?;
+            }
+            _ => {
+                selection.parse(arg, &mut args)?;
+            }
+        }
+    }
+
+    Ok(Command::Bench(BenchOptions {
+        selection: selection.build()?,
+        iterations,
+        warmup
+    }))
+}
+
+fn parse_new_day(args: &[&str], available_days: u32) -> Result<Command, String> {
+    let next = available_days + 1;
+
+    match args {
+        [day] => match day.parse::<u32>() {
+            Ok(day) if day == next => Ok(Command::NewDay(day)),
+            _ => Err(format!("Invalid day {:?}, the next day is {}", day, next))
+        },
+        [] => Err("Missing day to create".to_owned()),
+        [_, arg, ..] => Err(format!("Unexpected argument {:?}", arg))
+    }
+}
+
+fn parse_fetch(args: &[&str]) -> Result<Command, String> {
+    let mut day = None;
+    let mut base_url = fetch::DEFAULT_BASE_URL.to_owned();
+    let mut session_file = PathBuf::from(fetch::DEFAULT_SESSION_FILE);
+    let mut directory = PathBuf::from(input::DEFAULT_DIRECTORY);
+    let mut args = Args::new(args);
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--base-url" => {
+                base_url = args.value(arg)?.to_owned();
+            }
+            "--session-file" => {
+                session_file = args.value(arg)?.into();
+            }
+            "--input-dir" => {
+                directory = args.value(arg)?.into();
+            }
+            arg if arg.starts_with("--") => {
+                return Err(format!("Unknown option {:?}", arg));
+            }
+            arg => {
+                if day.is_some() {
+                    return Err(format!("Unexpected argument {:?}", arg));
+                }
+
+                // Inputs can be fetched before the day is implemented.
+                day = Some(parse_day(arg, 25)?);
+            }
+        }
+    }
+
+    Ok(Command::Fetch(FetchOptions {
+        day: day.ok_or("Missing day to fetch")?,
+        base_url,
+        session_file,
+        directory
+    }))
+}
+
+fn parse_submit(args: &[&str], available_days: u32) -> Result<Command, String> {
+    let mut positional = Vec::new();
+    let mut input = Input::default();
+    let mut base_url = fetch::DEFAULT_BASE_URL.to_owned();
+    let mut session_file = PathBuf::from(fetch::DEFAULT_SESSION_FILE);
+    let mut history = PathBuf::from(submit::DEFAULT_HISTORY_PATH);
+    let mut manifest = PathBuf::from(manifest::DEFAULT_PATH);
+    let mut args = Args::new(args);
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--input" => {
+                input = match args.value(arg)? {
+                    "-" => Input::Stdin,
+                    path => Input::File(path.into())
+                };
+            }
+            "--input-dir" => {
+                input = Input::Directory(args.value(arg)?.into());
+            }
+            "--base-url" => {
+                base_url = args.value(arg)?.to_owned();
+            }
+            "--session-file" => {
+                session_file = args.value(arg)?.into();
+            }
+            "--history" => {
+                history = args.value(arg)?.into();
+            }
+            "--manifest" => {
+                manifest = args.value(arg)?.into();
+            }
+            arg if arg.starts_with("--") => {
+                return Err(format!("Unknown option {:?}", arg));
+            }
+            arg => {
+                positional.push(arg);
+            }
+        }
+    }
+
+    match positional[..] {
+        [day, part] => Ok(Command::Submit(SubmitOptions {
+            day: parse_day(day, available_days)?,
+            part: parse_part(part)?,
+            input,
+            base_url,
+            session_file,
+            history,
+            manifest
+        })),
+        [] | [_] => Err("Missing day and part to submit".to_owned()),
+        [_, _, arg, ..] => Err(format!("Unexpected argument {:?}", arg))
+    }
+}
+
+fn parse_watch(args: &[&str], available_days: u32) -> Result<Command, String> {
+    let mut selection = SelectionBuilder::new(available_days);
+    let mut args = Args::new(args);
+
+    while let Some(arg) = args.next() {
+        selection.parse(arg, &mut args)?;
+    }
+
+    let selection = selection.build()?;
+
+    if selection.days.len() != 1 {
+        return Err("watch takes a single day".to_owned());
+    }
+
+    if selection.input == Input::Stdin {
+        return Err("watch can't read the input from stdin".to_owned());
+    }
+
+    Ok(Command::Watch(selection))
+}
+
+fn parse_repl(args: &[&str], available_days: u32) -> Result<Command, String> {
+    let mut selection = SelectionBuilder::new(available_days);
+    let mut args = Args::new(args);
+
+    while let Some(arg) = args.next() {
+        selection.parse(arg, &mut args)?;
+    }
+
+    let selection = selection.build()?;
+
+    if selection.days.len() != 1 {
+        return Err("repl takes a single day".to_owned());
+    }
+
+    // Commands are read from stdin.
+    if selection.input == Input::Stdin {
+        return Err("repl can't read the input from stdin".to_owned());
+    }
+
+    Ok(Command::Repl(selection))
+}
+
+fn parse_generate(args: &[&str], available_days: u32) -> Result<Command, String> {
+    let mut day = None;
+    let mut seed = 0;
+    let mut size = 10;
+    let mut args = Args::new(args);
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--seed" => {
+                let value = args.value(arg)?;
+                seed = value.parse::<u64>()
+                    .map_err(|_| format!("Invalid value {:?} for {}", value, arg))?;
+            }
+            "--size" => {
+                size = parse_count(arg, args.value(arg)?)?;
+
+                if size == 0 {
+                    return Err("--size must be at least 1".to_owned());
+                }
+            }
+            arg if arg.starts_with("--") => {
+                return Err(format!("Unknown option {:?}", arg));
+            }
+            arg => {
+                if day.is_some() {
+                    return Err(format!("Unexpected argument {:?}", arg));
+                }
+
+                day = Some(parse_day(arg, available_days)?);
+            }
+        }
+    }
+
+    Ok(Command::Generate(GenerateOptions {
+        day: day.ok_or("Missing day to generate")?,
+        seed,
+        size
+    }))
+}
+
+fn parse_fuzz(args: &[&str], available_days: u32) -> Result<Command, String> {
+    let mut days = None;
+    let mut seed = 0;
+    let mut iterations = 10000;
+    let mut args = Args::new(args);
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--seed" => {
+                let value = args.value(arg)?;
+                seed = value.parse::<u64>()
+                    .map_err(|_| format!("Invalid value {:?} for {}", value, arg))?;
+            }
+            "--iterations" => {
+                iterations = parse_count(arg, args.value(arg)?)?;
+
+                if iterations == 0 {
+                    return Err("--iterations must be at least 1".to_owned());
+                }
+            }
+            arg if arg.starts_with("--") => {
+                return Err(format!("Unknown option {:?}", arg));
+            }
+            arg => {
+                if days.is_some() {
+                    return Err(format!("Unexpected argument {:?}", arg));
+                }
+
+                days = Some(parse_days(arg, available_days)?);
+            }
+        }
+    }
+
+    Ok(Command::Fuzz(FuzzOptions {
+        days: days.ok_or("Missing days to fuzz")?,
+        seed,
+        iterations
+    }))
+}
+
+struct Args<'a> {
+    args: &'a [&'a str],
+    i: usize
+}
+
+impl<'a> Args<'a> {
+    fn new(args: &'a [&'a str]) -> Self {
+        Self { args, i: 0 }
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let result = self.args.get(self.i).copied();
+        self.i += 1;
+        result
+    }
+
+    fn value(&mut self, option: &str) -> Result<&'a str, String> {
+        self.next().ok_or_else(|| format!("Missing value for {}", option))
+    }
+}
+
+struct SelectionBuilder {
+    available_days: u32,
+    days: Option<Vec<u32>>,
+    parts: Vec<u32>,
+    input: Input
+}
+
+impl SelectionBuilder {
+    fn new(available_days: u32) -> Self {
+        Self {
+            available_days,
+            days: None,
+            parts: vec![1, 2],
+            input: Input::default()
+        }
+    }
+
+    fn parse(&mut self, arg: &str, args: &mut Args) -> Result<(), String> {
+        match arg {
+            "--part" => {
+                self.parts = vec![parse_part(args.value(arg)?)?];
+            }
+            "--input" => {
+                self.input = match args.value(arg)? {
+                    "-" => Input::Stdin,
+                    path => Input::File(path.into())
+                };
+            }
+            "--input-dir" => {
+                self.input = Input::Directory(args.value(arg)?.into());
+            }
+            arg if arg.starts_with("--") => {
+                return Err(format!("Unknown option {:?}", arg));
+            }
+            arg => {
+                if self.days.is_some() {
+                    return Err(format!("Unexpected argument {:?}", arg));
+                }
+
+                self.days = Some(parse_days(arg, self.available_days)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn build(self) -> Result<Selection, String> {
+        let days = self.days.ok_or("Missing days to run")?;
+
+        if self.input.is_single_day() && days.len() != 1 {
+            return Err("--input can only be used with a single day".to_owned());
+        }
+
+        Ok(Selection { days, parts: self.parts, input: self.input })
+    }
+}
+
+fn parse_part(s: &str) -> Result<u32, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("Invalid part {:?}, expected 1 or 2", s))
+    }
+}
+
+fn parse_count(option: &str, s: &str) -> Result<usize, String> {
+    s.parse::<usize>()
+        .map_err(|_| format!("Invalid value {:?} for {}", s, option))
+}
+
+fn parse_days(s: &str, available_days: u32) -> Result<Vec<u32>, String> {
+    if s == "all" {
+        return Ok((1..=available_days).collect());
+    }
+
+    let (start, end) = if let Some((start, end)) = s.split_once("..") {
+        let end = end.strip_prefix('=').unwrap_or(end);
+        (parse_day(start, available_days)?, parse_day(end, available_days)?)
+    } else {
+        let day = parse_day(s, available_days)?;
+        (day, day)
+    };
+
+    if start > end {
+        return Err(format!("Empty day range {:?}", s));
+    }
+
+    Ok((start..=end).collect())
+}
+
+fn parse_day(s: &str, available_days: u32) -> Result<u32, String> {
+    let day = s.parse::<u32>()
+        .map_err(|_| format!("Invalid day {:?}", s))?;
+
+    if (1..=available_days).contains(&day) {
+        Ok(day)
+    } else {
+        Err(format!("Day {} is not available (1 to {})", day, available_days))
+    }
+}
+
+#[cfg(test)]
+fn selection(days: Vec<u32>) -> Selection {
+    Selection { days, parts: vec![1, 2], input: Input::default() }
+}
+
+#[cfg(test)]
+fn run_options(selection: Selection) -> RunOptions {
+    RunOptions {
+        selection,
+        manifest: PathBuf::from(manifest::DEFAULT_PATH),
+        record: false,
+        format: Format::Text,
+        jobs: 1,
+        explain: false
+    }
+}
+
+#[test]
+fn test_parse_args() {
+    let data = [
+        (vec!["run", "all"],
+         run_options(selection(vec![1, 2, 3, 4, 5, 6, 7]))),
+        (vec!["run", "5", "--part", "2"],
+         run_options(Selection { parts: vec![2], ..selection(vec![5]) })),
+        (vec!["run", "--part", "1", "3..5"],
+         run_options(Selection { parts: vec![1], ..selection(vec![3, 4, 5]) })),
+        (vec!["run", "3..=4"],
+         run_options(selection(vec![3, 4]))),
+        (vec!["run", "2", "--input", "mine.txt"],
+         run_options(Selection {
+             input: Input::File("mine.txt".into()),
+             ..selection(vec![2])
+         })),
+        (vec!["run", "2", "--input", "-"],
+         run_options(Selection { input: Input::Stdin, ..selection(vec![2]) })),
+        (vec!["run", "all", "--input-dir", "ci"],
+         run_options(Selection {
+             input: Input::Directory("ci".into()),
+             ..selection(vec![1, 2, 3, 4, 5, 6, 7])
+         })),
+        (vec!["run", "1", "--manifest", "ci/answers.txt", "--record"],
+         RunOptions {
+             manifest: "ci/answers.txt".into(),
+             record: true,
+             ..run_options(selection(vec![1]))
+         }),
+        (vec!["run", "1", "--format", "json"],
+         RunOptions { format: Format::Json, ..run_options(selection(vec![1])) }),
+        (vec!["run", "1", "--format", "csv"],
+         RunOptions { format: Format::Csv, ..run_options(selection(vec![1])) }),
+        (vec!["run", "all", "--jobs", "4"],
+         RunOptions { jobs: 4, ..run_options(selection(vec![1, 2, 3, 4, 5, 6, 7])) }),
+        (vec!["run", "3", "--explain", "--format", "json"],
+         RunOptions { explain: true, format: Format::Json, ..run_options(selection(vec![3])) })
+    ];
+
+    for (args, options) in data {
+        assert_eq!(parse_args(&args, 7), Ok(Command::Run(options)), "{:?}", args);
+    }
+}
+
+#[test]
+fn test_parse_bench_args() {
+    let data = [
+        (vec!["bench", "5"],
+         BenchOptions { selection: selection(vec![5]), iterations: 10, warmup: 1 }),
+        (vec!["bench", "1..2", "--part", "2", "--iterations", "50", "--warmup", "0"],
+         BenchOptions {
+             selection: Selection { parts: vec![2], ..selection(vec![1, 2]) },
+             iterations: 50,
+             warmup: 0
+         })
+    ];
+
+    for (args, options) in data {
+        assert_eq!(parse_args(&args, 7), Ok(Command::Bench(options)), "{:?}", args);
+    }
+}
+
+#[test]
+fn test_parse_new_day_args() {
+    assert_eq!(parse_args(&["new-day", "8"], 7), Ok(Command::NewDay(8)));
+
+    for args in [
+        vec!["new-day"],
+        vec!["new-day", "7"],
+        vec!["new-day", "9"],
+        vec!["new-day", "eight"],
+        vec!["new-day", "8", "--part", "1"]
+    ] {
+        assert!(parse_args(&args, 7).is_err(), "{:?}", args);
+    }
+}
+
+#[test]
+fn test_parse_fetch_args() {
+    assert_eq!(
+        parse_args(&["fetch", "12"], 7),
+        Ok(Command::Fetch(FetchOptions {
+            day: 12,
+            base_url: fetch::DEFAULT_BASE_URL.to_owned(),
+            session_file: fetch::DEFAULT_SESSION_FILE.into(),
+            directory: input::DEFAULT_DIRECTORY.into()
+        }))
+    );
+    assert_eq!(
+        parse_args(
+            &["fetch", "--base-url", "http://localhost:8000", "3", "--session-file",
+              "token", "--input-dir", "ci"],
+            7
+        ),
+        Ok(Command::Fetch(FetchOptions {
+            day: 3,
+            base_url: "http://localhost:8000".to_owned(),
+            session_file: "token".into(),
+            directory: "ci".into()
+        }))
+    );
+
+    for args in [
+        vec!["fetch"],
+        vec!["fetch", "26"],
+        vec!["fetch", "1..3"],
+        vec!["fetch", "3", "4"],
+        vec!["fetch", "3", "--base-url"],
+        vec!["fetch", "3", "--part", "1"]
+    ] {
+        assert!(parse_args(&args, 7).is_err(), "{:?}", args);
+    }
+}
+
+#[cfg(test)]
+fn submit_options(day: u32, part: u32) -> SubmitOptions {
+    SubmitOptions {
+        day,
+        part,
+        input: Input::default(),
+        base_url: fetch::DEFAULT_BASE_URL.to_owned(),
+        session_file: fetch::DEFAULT_SESSION_FILE.into(),
+        history: submit::DEFAULT_HISTORY_PATH.into(),
+        manifest: manifest::DEFAULT_PATH.into()
+    }
+}
+
+#[test]
+fn test_parse_submit_args() {
+    assert_eq!(parse_args(&["submit", "5", "2"], 7), Ok(Command::Submit(submit_options(5, 2))));
+    assert_eq!(
+        parse_args(
+            &["submit", "--input", "-", "1", "1", "--base-url", "http://localhost:8000",
+              "--history", "h.txt"],
+            7
+        ),
+        Ok(Command::Submit(SubmitOptions {
+            input: Input::Stdin,
+            base_url: "http://localhost:8000".to_owned(),
+            history: "h.txt".into(),
+            ..submit_options(1, 1)
+        }))
+    );
+
+    for args in [
+        vec!["submit"],
+        vec!["submit", "5"],
+        vec!["submit", "5", "3"],
+        vec!["submit", "8", "1"],
+        vec!["submit", "all", "1"],
+        vec!["submit", "5", "1", "2"],
+        vec!["submit", "5", "1", "--part", "2"]
+    ] {
+        assert!(parse_args(&args, 7).is_err(), "{:?}", args);
+    }
+}
+
+#[test]
+fn test_parse_watch_args() {
+    assert_eq!(parse_args(&["watch", "7"], 7), Ok(Command::Watch(selection(vec![7]))));
+    assert_eq!(
+        parse_args(&["watch", "7", "--part", "2", "--input", "mine.txt"], 7),
+        Ok(Command::Watch(Selection {
+            parts: vec![2],
+            input: Input::File("mine.txt".into()),
+            ..selection(vec![7])
+        }))
+    );
+
+    for args in [
+        vec!["watch"],
+        vec!["watch", "all"],
+        vec!["watch", "3..4"],
+        vec!["watch", "3", "--input", "-"],
+        vec!["watch", "3", "--jobs", "2"]
+    ] {
+        assert!(parse_args(&args, 7).is_err(), "{:?}", args);
+    }
+}
+
+#[test]
+fn test_parse_repl_args() {
+    assert_eq!(parse_args(&["repl", "5"], 7), Ok(Command::Repl(selection(vec![5]))));
+    assert_eq!(
+        parse_args(&["repl", "--input", "examples/5/sample.txt", "5", "--part", "1"], 7),
+        Ok(Command::Repl(Selection {
+            parts: vec![1],
+            input: Input::File("examples/5/sample.txt".into()),
+            ..selection(vec![5])
+        }))
+    );
+
+    for args in [
+        vec!["repl"],
+        vec!["repl", "all"],
+        vec!["repl", "8"],
+        vec!["repl", "3", "--input", "-"]
+    ] {
+        assert!(parse_args(&args, 7).is_err(), "{:?}", args);
+    }
+}
+
+#[test]
+fn test_parse_generate_args() {
+    assert_eq!(
+        parse_args(&["gen", "5"], 7),
+        Ok(Command::Generate(GenerateOptions { day: 5, seed: 0, size: 10 }))
+    );
+    assert_eq!(
+        parse_args(&["gen", "--size", "100", "2", "--seed", "18446744073709551615"], 7),
+        Ok(Command::Generate(GenerateOptions { day: 2, seed: u64::MAX, size: 100 }))
+    );
+
+    for args in [
+        vec!["gen"],
+        vec!["gen", "8"],
+        vec!["gen", "all"],
+        vec!["gen", "5", "--seed", "-1"],
+        vec!["gen", "5", "--size", "0"],
+        vec!["gen", "5", "--part", "1"]
+    ] {
+        assert!(parse_args(&args, 7).is_err(), "{:?}", args);
+    }
+}
+
+#[test]
+fn test_parse_fuzz_args() {
+    assert_eq!(
+        parse_args(&["fuzz", "all"], 7),
+        Ok(Command::Fuzz(FuzzOptions { days: vec![1, 2, 3, 4, 5, 6, 7], seed: 0, iterations: 10000 }))
+    );
+    assert_eq!(
+        parse_args(&["fuzz", "--iterations", "50", "2..3", "--seed", "9"], 7),
+        Ok(Command::Fuzz(FuzzOptions { days: vec![2, 3], seed: 9, iterations: 50 }))
+    );
+
+    for args in [
+        vec!["fuzz"],
+        vec!["fuzz", "8"],
+        vec!["fuzz", "1", "2"],
+        vec!["fuzz", "5", "--iterations", "0"],
+        vec!["fuzz", "5", "--size", "1"]
+    ] {
+        assert!(parse_args(&args, 7).is_err(), "{:?}", args);
+    }
+}
+
+#[test]
+fn test_parse_args_errors() {
+    let data = [
+        vec![],
+        vec!["walk"],
+        vec!["run"],
+        vec!["run", "8"],
+        vec!["run", "0..3"],
+        vec!["run", "5..3"],
+        vec!["run", "5", "--part"],
+        vec!["run", "5", "--part", "3"],
+        vec!["run", "5", "6"],
+        vec!["run", "5", "--verbose"],
+        vec!["run", "5", "--input"],
+        vec!["run", "all", "--input", "mine.txt"],
+        vec!["run", "1", "--manifest"],
+        vec!["run", "1", "--format", "xml"],
+        vec!["run", "1", "--jobs", "0"],
+        vec!["run", "1", "--explain", "--format", "csv"],
+        vec!["bench", "1", "--explain"],
+        vec!["bench", "1", "--format", "json"],
+        vec!["run", "1", "--iterations", "5"],
+        vec!["bench", "1", "--iterations", "0"],
+        vec!["bench", "1", "--warmup", "-1"],
+        vec!["bench", "1", "--record"]
+    ];
+
+    for args in data {
+        assert!(parse_args(&args, 7).is_err(), "{:?}", args);
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// A multiset: how many times each key was added. Keys are kept in the order
+// they were first added, so that ties always break the same way.
+#[derive(Clone, Debug)]
+pub struct Counter<K> {
+    entries: Vec<(K, usize)>,
+    indices: HashMap<K, usize>
+}
+
+impl<K: Clone + Eq + Hash> Counter<K> {
+    pub fn new() -> Self {
+        Self { entries: Vec::new(), indices: HashMap::new() }
+    }
+
+    pub fn add(&mut self, key: K) {
+        self.add_n(key, 1);
+    }
+
+    pub fn add_n(&mut self, key: K, n: usize) {
+        if n == 0 {
+            return;
+        }
+
+        match self.indices.get(&key) {
+            Some(&i) => self.entries[i].1 += n,
+            None => {
+                self.indices.insert(key.clone(), self.entries.len());
+                self.entries.push((key, n));
+            }
+        }
+    }
+
+    // Removes the key altogether, returning how many times it was there.
+    pub fn remove(&mut self, key: &K) -> usize {
+        let Some(i) = self.indices.remove(key) else {
+            return 0;
+        };
+
+        let (_, count) = self.entries.remove(i);
+
+        for index in self.indices.values_mut() {
+            if *index > i {
+                *index -= 1;
+            }
+        }
+
+        count
+    }
+
+    pub fn get(&self, key: &K) -> usize {
+        self.indices.get(key).map_or(0, |&i| self.entries[i].1)
+    }
+
+    // The number of distinct keys.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // The number of keys added, counting repeats.
+    pub fn total(&self) -> usize {
+        self.entries.iter().map(|&(_, count)| count).sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, usize)> {
+        self.entries.iter().map(|(key, count)| (key, *count))
+    }
+
+    // Most frequent first, keys added first winning ties.
+    pub fn most_common(&self) -> Vec<(&K, usize)> {
+        let mut result: Vec<(&K, usize)> = self.iter().collect();
+        result.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        result
+    }
+
+    // The counts alone, highest first, e.g. [3, 2] for a full house whatever
+    // its cards are.
+    pub fn signature(&self) -> Vec<usize> {
+        let mut result: Vec<usize> = self.entries.iter().map(|&(_, count)| count).collect();
+        result.sort_by(|a, b| b.cmp(a));
+        result
+    }
+
+    // The lowest count of each key in both, in the order of self.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.iter()
+            .map(|(key, count)| (key.clone(), count.min(other.get(key))))
+            .collect()
+    }
+
+    // The highest count of each key in either, keys of self first.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+
+        for (key, count) in other.iter() {
+            let missing = count.saturating_sub(result.get(key));
+            result.add_n(key.clone(), missing);
+        }
+
+        result
+    }
+}
+
+impl<K: Clone + Eq + Hash> Default for Counter<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Two counters are equal when they count the same keys the same number of
+// times, in whatever order they were added.
+impl<K: Clone + Eq + Hash> PartialEq for Counter<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(key, count)| other.get(key) == count)
+    }
+}
+
+impl<K: Clone + Eq + Hash> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(keys: I) -> Self {
+        let mut result = Self::new();
+
+        for key in keys {
+            result.add(key);
+        }
+
+        result
+    }
+}
+
+impl<K: Clone + Eq + Hash> FromIterator<(K, usize)> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = (K, usize)>>(counts: I) -> Self {
+        let mut result = Self::new();
+
+        for (key, count) in counts {
+            result.add_n(key, count);
+        }
+
+        result
+    }
+}
+
+#[test]
+fn test_counts() {
+    let mut counter: Counter<char> = "abracadabra".chars().collect();
+    assert_eq!((counter.get(&'a'), counter.get(&'r'), counter.get(&'z')), (5, 2, 0));
+    assert_eq!((counter.len(), counter.total()), (5, 11));
+    assert_eq!(counter.most_common(), [(&'a', 5), (&'b', 2), (&'r', 2), (&'c', 1), (&'d', 1)]);
+    assert_eq!(counter.signature(), [5, 2, 2, 1, 1]);
+
+    assert_eq!(counter.remove(&'b'), 2);
+    assert_eq!(counter.remove(&'b'), 0);
+    assert_eq!(counter.most_common()[1], (&'r', 2));
+    counter.add('c');
+    assert_eq!(counter.get(&'c'), 2);
+    assert_eq!(counter.most_common()[1..], [(&'r', 2), (&'c', 2), (&'d', 1)]);
+
+    counter.add_n('z', 0);
+    assert_eq!(counter.get(&'z'), 0);
+    assert!(Counter::<char>::new().is_empty());
+}
+
+#[test]
+fn test_intersection_and_union() {
+    let a: Counter<char> = "aaabbc".chars().collect();
+    let b: Counter<char> = "abbbd".chars().collect();
+    assert_eq!(a.intersection(&b), "abb".chars().collect());
+    assert_eq!(a.intersection(&b).len(), 2);
+    assert_eq!(a.union(&b), "aaabbbcd".chars().collect());
+    assert_eq!(b.union(&a).iter().map(|(&key, _)| key).collect::<String>(), "abdc");
+    assert_eq!(a.intersection(&Counter::new()).total(), 0);
+    assert_eq!(a.union(&Counter::new()), a);
+    assert_ne!(a, b);
+}
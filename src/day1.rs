@@ -1,22 +1,81 @@
-use std::io::BufRead;
+use crate::error::{LineError, ParseError, SolveError};
+use crate::parser;
+use crate::solution::{self, Answer, Solution, Trace};
 
-pub fn part1() -> u32 {
-    let file = std::fs::File::open("problem_inputs/1.txt").unwrap();
-    let lines = std::io::BufReader::new(file).lines();
-    lines.map(|line| part1_parse_line(line.unwrap().as_str())).sum()
+const DAY: u32 = 1;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Parsed = Vec<String>;
+
+    fn day(&self) -> u32 {
+        DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        parser::lines(input.lines(), DAY, |line| {
+            if part2_extract_all_digits(line).is_empty() {
+                Err(LineError::whole(line, "expected at least one digit"))
+            } else {
+                Ok(line.to_owned())
+            }
+        })
+    }
+
+    fn part1(&self, lines: &Vec<String>) -> Result<Answer, SolveError> {
+        calibration_sum(lines, 1, part1_parse_line)
+    }
+
+    fn part2(&self, lines: &Vec<String>) -> Result<Answer, SolveError> {
+        calibration_sum(lines, 2, part2_parse_line)
+    }
+
+    fn explain(&self, lines: &Vec<String>, part: u32) -> Result<Trace, SolveError> {
+        let parse_line = if part == 1 { part1_parse_line } else { part2_parse_line };
+        let values = parser::lines(lines.iter().map(String::as_str), DAY, parse_line)?;
+        Ok(lines.iter()
+            .zip(values)
+            .enumerate()
+            .map(|(i, (line, value))| format!(
+                "line {}: {} has first digit {} and last digit {}, so {}",
+                i + 1,
+                line,
+                value / 10,
+                value % 10,
+                value
+            ))
+            .collect())
+    }
 }
 
-fn part1_parse_line(line: &str) -> u32 {
+fn calibration_sum(
+    lines: &[String],
+    part: u32,
+    parse_line: fn(&str) -> Result<u32, LineError>
+) -> Result<Answer, SolveError> {
+    let values = parser::lines(lines.iter().map(String::as_str), DAY, parse_line)?;
+    solution::checked_sum(
+        DAY,
+        part,
+        "the sum of calibration values",
+        values.into_iter().map(|value| Some(Answer::from(value)))
+    )
+}
+
+pub fn part1_parse_line(line: &str) -> Result<u32, LineError> {
     let all_digits: Vec<u32> = line.chars()
         .filter_map(|char| char.to_digit(10))
         .collect();
     let result_digits: (u32, u32) = match all_digits.len() {
         1 => (all_digits[0], all_digits[0]),
         2.. => (all_digits[0], *all_digits.last().unwrap()),
-        _ => unreachable!()
+        _ => {
+            return Err(LineError::whole(line, "expected at least one numeric digit"));
+        }
     };
 
-    result_digits.0 * 10 + result_digits.1
+    Ok(result_digits.0 * 10 + result_digits.1)
 }
 
 #[test]
@@ -29,29 +88,24 @@ fn test_part1() {
     ];
 
     for (input, output) in data {
-        assert_eq!(part1_parse_line(input), output, "{}", input);
+        assert_eq!(part1_parse_line(input), Ok(output), "{}", input);
     }
 }
 
-pub fn part2() -> u32 {
-    let file = std::fs::File::open("problem_inputs/1.txt").unwrap();
-    let lines = std::io::BufReader::new(file).lines();
-    lines.map(|line| part2_parse_line(line.unwrap().as_str())).sum()
-}
-
-fn part2_parse_line(line: &str) -> u32 {
+pub fn part2_parse_line(line: &str) -> Result<u32, LineError> {
     let all_digits: Vec<u32> = part2_extract_all_digits(line);
     let result_digits: (u32, u32) = match all_digits.len() {
         1 => (all_digits[0], all_digits[0]),
         2.. => (all_digits[0], *all_digits.last().unwrap()),
-        _ => unreachable!()
+        _ => {
+            return Err(LineError::whole(line, "expected at least one digit"));
+        }
     };
 
-    result_digits.0 * 10 + result_digits.1
+    Ok(result_digits.0 * 10 + result_digits.1)
 }
 
 fn part2_extract_all_digits(line: &str) -> Vec<u32> {
-    let chars: Vec<char> = line.chars().collect();
     let mut result: Vec<u32> = Vec::new();
     let words = [
         "one",
@@ -65,20 +119,17 @@ fn part2_extract_all_digits(line: &str) -> Vec<u32> {
         "nine"
     ];
 
-    for (i, c) in chars.iter().enumerate() {
+    for (i, c) in line.char_indices() {
         match c.to_digit(10) {
             Some(digit) => {
                 result.push(digit);
             }
             None => {
-                for j in i..(chars.len()) {
-                    let word = chars[i..=j].iter().collect::<String>();
-                    let position = words.iter()
-                        .position(|&x| x == word.as_str());
-
-                    if let Some(position) = position {
-                        result.push((position + 1).try_into().unwrap());
-                    }
+                let position = words.iter()
+                    .position(|&word| line[i..].starts_with(word));
+
+                if let Some(position) = position {
+                    result.push((position + 1).try_into().unwrap());
                 }
             }
         }
@@ -100,6 +151,32 @@ fn test_part2() {
     ];
 
     for (input, output) in data {
-        assert_eq!(part2_parse_line(input), output, "{}", input);
+        assert_eq!(part2_parse_line(input), Ok(output), "{}", input);
     }
 }
+
+#[test]
+fn test_errors() {
+    let error = Day1.parse("1abc2\nabcdef").err().unwrap();
+    assert_eq!((error.line, error.column, error.width), (2, 1, 6));
+
+    let lines = Day1.parse("two1nine\neightwothree").unwrap();
+    assert_eq!(Day1.part2(&lines), Ok(29 + 83));
+    assert!(matches!(Day1.part1(&lines), Err(SolveError::Parse(ParseError { line: 2, .. }))));
+}
+
+#[test]
+fn test_explain() {
+    let lines = Day1.parse("two1nine\neightwothree").unwrap();
+    assert_eq!(
+        Solution::explain(&Day1, &lines, 2),
+        Ok(vec![
+            "line 1: two1nine has first digit 2 and last digit 9, so 29".to_owned(),
+            "line 2: eightwothree has first digit 8 and last digit 3, so 83".to_owned()
+        ])
+    );
+    assert!(matches!(
+        Solution::explain(&Day1, &lines, 1),
+        Err(SolveError::Parse(ParseError { line: 2, .. }))
+    ));
+}
@@ -1,39 +1,91 @@
-use std::io::BufRead;
-
-pub fn part1() -> u32 {
-    let file = std::fs::File::open("problem_inputs/2.txt").unwrap();
-    let lines = std::io::BufReader::new(file).lines();
-    let games = lines.map(|line| parse_line(line.unwrap().as_str()));
-    let mut result: u32 = 0;
-
-    for (i, game) in games.enumerate() {
-        if game.is_possible() {
-            let game_id: u32 = (i + 1).try_into().unwrap();
-            result += game_id;
-        }
+use crate::error::{LineError, ParseError, SolveError};
+use crate::parser;
+use crate::solution::{self, Answer, Solution, Trace};
+
+const DAY: u32 = 2;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Parsed = Vec<Game>;
+
+    fn day(&self) -> u32 {
+        DAY
     }
 
-    result
-}
+    fn parse(&self, input: &str) -> Result<Vec<Game>, ParseError> {
+        parser::lines(input.lines(), DAY, parse_line)
+    }
+
+    fn part1(&self, games: &Vec<Game>) -> Result<Answer, SolveError> {
+        let ids = games.iter()
+            .filter(|game| game.is_possible())
+            .map(|game| Some(Answer::from(game.id)));
+        solution::checked_sum(DAY, 1, "the sum of game ids", ids)
+    }
 
-pub fn part2() -> u32 {
-    let file = std::fs::File::open("problem_inputs/2.txt").unwrap();
-    let lines = std::io::BufReader::new(file).lines();
-    let games = lines.map(|line| parse_line(line.unwrap().as_str()));
-    games.map(|game| game.minimum_required().power()).sum()
+    fn part2(&self, games: &Vec<Game>) -> Result<Answer, SolveError> {
+        let powers = games.iter()
+            .map(|game| Answer::try_from(game.minimum_required().power()).ok());
+        solution::checked_sum(DAY, 2, "the sum of powers", powers)
+    }
+
+    fn explain(&self, games: &Vec<Game>, part: u32) -> Result<Trace, SolveError> {
+        Ok(games.iter()
+            .map(|game| {
+                let minimum = game.minimum_required();
+
+                if part == 2 {
+                    return format!("game {}: needs {}, power {}", game.id, minimum, minimum.power());
+                }
+
+                let over: Vec<String> = [
+                    (minimum.red, LIMIT.red, "red"),
+                    (minimum.green, LIMIT.green, "green"),
+                    (minimum.blue, LIMIT.blue, "blue")
+                ]
+                    .iter()
+                    .filter(|(count, limit, _)| count > limit)
+                    .map(|(count, limit, color)| format!("{} {} over the limit of {}", count, color, limit))
+                    .collect();
+
+                if over.is_empty() {
+                    format!("game {}: possible, adds {}", game.id, game.id)
+                } else {
+                    format!("game {}: impossible, {}", game.id, over.join(", "))
+                }
+            })
+            .collect())
+    }
 }
 
+// The cubes in the bag for part 1.
+const LIMIT: Cubes = Cubes { red: 12, green: 13, blue: 14 };
+
 #[derive(Debug, PartialEq)]
-struct Game {
-    sets: Vec<Cubes>
+pub struct Game {
+    pub id: u32,
+    pub sets: Vec<Cubes>
+}
+
+impl std::fmt::Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Game {}:", self.id)?;
+
+        for (i, set) in self.sets.iter().enumerate() {
+            write!(f, "{} {}", if i == 0 { "" } else { ";" }, set)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Game {
-    fn is_possible(&self) -> bool {
-        self.sets.iter().all(|c| c.red <= 12 && c.green <= 13 && c.blue <= 14)
+    pub fn is_possible(&self) -> bool {
+        self.sets.iter().all(|c| c.red <= LIMIT.red && c.green <= LIMIT.green && c.blue <= LIMIT.blue)
     }
 
-    fn minimum_required(&self) -> Cubes {
+    pub fn minimum_required(&self) -> Cubes {
         let mut max = Cubes { red: 0, green: 0, blue: 0 };
 
         for set in &self.sets {
@@ -54,61 +106,67 @@ impl Game {
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct Cubes {
-    red: u32,
-    green: u32,
-    blue: u32
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cubes {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32
+}
+
+// Colors without cubes are left out, unless there are no cubes at all.
+impl std::fmt::Display for Cubes {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let counts = [(self.red, "red"), (self.green, "green"), (self.blue, "blue")];
+        let mut counts = counts.iter().filter(|(count, _)| *count > 0).peekable();
+
+        if counts.peek().is_none() {
+            return write!(f, "0 red");
+        }
+
+        for (i, (count, color)) in counts.enumerate() {
+            write!(f, "{}{} {}", if i == 0 { "" } else { ", " }, count, color)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Cubes {
-    fn power(self) -> u32 {
-        self.red * self.green * self.blue
+    // Wide enough for any three counts.
+    pub fn power(self) -> u128 {
+        self.red as u128 * self.green as u128 * self.blue as u128
     }
 }
 
-fn parse_line(line: &str) -> Game {
-    let sets = line.chars()
-        .skip_while(|&c| c != ':')
-        .skip(2)
-        .collect::<String>();
-    parse_sets(sets.as_str())
+pub fn parse_line(line: &str) -> Result<Game, LineError> {
+    let (header, sets) = parser::split_once(line, ":", "Game <id>: <sets>")?;
+    let id = parser::label(header, "Game ", "Game <id>")?;
+    let id = parser::integer(id, "a game id")?;
+    Ok(Game { id, sets: parse_sets(sets)? })
 }
 
-fn parse_sets(s: &str) -> Game {
-    let split = s.split("; ");
-    Game { sets: split.map(parse_set).collect() }
+fn parse_sets(s: &str) -> Result<Vec<Cubes>, LineError> {
+    parser::separated(s, ';', parse_set)
 }
 
-fn parse_set(s: &str) -> Cubes {
-    let split: Vec<&str> = s.split(", ").collect();
-    let mut red = 0;
-    let mut green = 0;
-    let mut blue = 0;
-
-    for s in split {
-        let x: Vec<_> = s.split(' ').collect();
-        assert_eq!(x.len(), 2);
-        let quantity = x[0].parse::<u32>().unwrap();
-        let color = x[1];
-
-        match color {
-            "red" => {
-                red = quantity;
-            }
-            "green" => {
-                green = quantity;
-            }
-            "blue" => {
-                blue = quantity;
-            }
-            _ => {
-                unreachable!();
-            }
+fn parse_set(s: &str) -> Result<Cubes, LineError> {
+    let colors = [("red", 0), ("green", 1), ("blue", 2)];
+    let mut counts = [None; 3];
+    let cubes = parser::separated(s, ',', |cubes| {
+        let [quantity, color] = parser::fields(cubes, "<quantity> <color>")?;
+        Ok((parser::integer(quantity, "a quantity")?, color))
+    })?;
+
+    for (quantity, color) in cubes {
+        let i = parser::one_of(color, &colors, "color")?;
+
+        if counts[i].replace(quantity).is_some() {
+            return Err(LineError::new(color, format!("{} given twice", color)));
         }
     }
 
-    Cubes { red, green, blue }
+    let [red, green, blue] = counts.map(|count| count.unwrap_or(0));
+    Ok(Cubes { red, green, blue })
 }
 
 #[test]
@@ -135,7 +193,52 @@ fn test_part1_parsing() {
               Cubes { red: 1, green: 2, blue: 2 }])
     ];
 
-    for (line, sets) in data {
-        assert_eq!(parse_line(line), Game { sets });
+    for (i, (line, sets)) in data.into_iter().enumerate() {
+        assert_eq!(parse_line(line), Ok(Game { id: i as u32 + 1, sets }));
     }
 }
+
+#[test]
+fn test_parsing_errors() {
+    let data = [
+        ("Game 1 3 blue", 1, 13),
+        ("Gaem 1: 3 blue", 1, 6),
+        ("Game one: 3 blue", 6, 3),
+        ("Game 1: 3 blue; 2 purple", 19, 6),
+        ("Game 1: 3 blue, x red", 17, 1),
+        ("Game 1: 3 blue,, 1 red", 16, 0),
+        ("Game 1: 3 blue 1 red", 9, 12),
+        ("Game 1: 3 red, 4 red", 18, 3)
+    ];
+
+    for (line, column, width) in data {
+        let error = Day2.parse(line).err().unwrap();
+        assert_eq!((error.line, error.column, error.width), (1, column, width), "{}", line);
+    }
+}
+
+#[test]
+fn test_explain() {
+    let games = Day2.parse("Game 1: 3 blue, 4 red\nGame 3: 8 green, 20 red; 15 blue").unwrap();
+    assert_eq!(
+        Solution::explain(&Day2, &games, 1),
+        Ok(vec![
+            "game 1: possible, adds 1".to_owned(),
+            "game 3: impossible, 20 red over the limit of 12, 15 blue over the limit of 14".to_owned()
+        ])
+    );
+    assert_eq!(
+        Solution::explain(&Day2, &games, 2).unwrap()[1],
+        "game 3: needs 20 red, 8 green, 15 blue, power 2400"
+    );
+}
+
+#[test]
+fn test_overflow() {
+    let games = Day2.parse("Game 1: 1 red\nGame 2: 4294967295 red, 4294967295 green, 4294967295 blue").unwrap();
+    assert_eq!(
+        Day2.part2(&games),
+        Err(SolveError::Overflow { day: 2, part: 2, what: "the sum of powers" })
+    );
+    assert_eq!(games[1].minimum_required().power(), 4294967295u128.pow(3));
+}
@@ -1,226 +1,190 @@
-use std::io::BufRead;
+use crate::error::{LineError, ParseError, SolveError};
+use crate::grid::{Grid, Point};
+use crate::solution::{self, Answer, Solution, Trace};
 
-struct Schematic {
-    lines: Vec<String>
-}
-
-impl Schematic {
-    fn get(&self, x: u32, y: u32) -> Option<char> {
-        self.lines.get(y as usize)
-            .and_then(|line| line.chars()
-                      .nth(x as usize))
-    }
-
-    fn numbers(&self) -> Vec<NumberMatch> {
-        let mut result = Vec::new();
-
-        for (y, line) in self.lines.iter().enumerate() {
-            let mut current_number_option: Option<NumberMatch> = None;
-
-            for (x, c) in line.chars().enumerate() {
-                let mut check = false;
-
-                if let Some(digit) = c.to_digit(10) {
-                    if let Some(number_match) = current_number_option {
-                        current_number_option = Some(
-                            NumberMatch {
-                                x: number_match.x,
-                                y: y as u32,
-                                value: number_match.value * 10 + digit,
-                                size: number_match.size + 1
-                            }
-                        );
-                    } else {
-                        current_number_option = Some(
-                            NumberMatch {
-                                x: x as u32,
-                                y: y as u32,
-                                value: digit,
-                                size: 1
-                            }
-                        );
-                    }
-                } else {
-                    check = true;
-                }
+const DAY: u32 = 3;
 
-                if x == line.len() - 1 {
-                    check = true;
-                }
+pub struct Day3;
 
-                if check {
-                    if let Some(number_match) = current_number_option {
-                        result.push(number_match);
-                        current_number_option = None;
-                    }
-                }
-            }
-        }
+impl Solution for Day3 {
+    type Parsed = Schematic;
 
-        result
+    fn day(&self) -> u32 {
+        DAY
     }
 
-    fn stars(&self) -> Vec<StarMatch> {
-        let mut result = Vec::new();
+    fn parse(&self, input: &str) -> Result<Schematic, ParseError> {
+        for (i, line) in input.lines().enumerate() {
+            // Numbers are only summed up later on, so they are checked here.
+            let too_large = line.split(|c: char| !c.is_ascii_digit())
+                .find(|number| !number.is_empty() && number.parse::<u32>().is_err());
 
-        for (y, line) in self.lines.iter().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                if c == '*' {
-                    result.push(
-                        StarMatch {
-                            x: x as u32,
-                            y: y as u32
-                        }
-                    );
-                }
+            if let Some(number) = too_large {
+                return Err(LineError::new(number, "number too large").locate(DAY, i, line));
             }
         }
 
-        result
+        let grid = Grid::parse(input, DAY, |c| {
+            if c.is_ascii() {
+                Ok(c)
+            } else {
+                Err(format!("unexpected character {:?}", c))
+            }
+        })?;
+        Ok(Schematic { grid })
     }
 
-    fn numbers_for_gear(&self, x: u32, y: u32) -> Vec<u32> {
-        assert_eq!(self.get(x, y), Some('*'));
+    fn part1(&self, schematic: &Schematic) -> Result<Answer, SolveError> {
+        part1_result(schematic)
+    }
 
-        self.numbers()
-            .iter()
-            .filter(|number|
-                    ((number.x)..(number.x + number.size))
-                    .any(|number_x: u32|
-                         is_neighbor_of(number_x, number.y, x, y)))
-            .map(|number| number.value)
-            .collect()
+    fn part2(&self, schematic: &Schematic) -> Result<Answer, SolveError> {
+        part2_result(schematic)
     }
-}
 
-#[derive(Debug)]
-struct NumberMatch {
-    x: u32,
-    y: u32,
-    value: u32,
-    size: u32
-}
+    fn explain(&self, schematic: &Schematic, part: u32) -> Result<Trace, SolveError> {
+        if part == 1 {
+            return Ok(schematic.numbers()
+                .iter()
+                .filter(|number| schematic.is_part_number(number))
+                .map(|number| format!(
+                    "line {}, column {}: part number {}",
+                    number.start.y + 1,
+                    number.start.x + 1,
+                    number.value
+                ))
+                .collect());
+        }
 
-struct StarMatch {
-    x: u32,
-    y: u32
+        Ok(schematic.stars()
+            .into_iter()
+            .filter_map(|star| {
+                let numbers = schematic.numbers_for_gear(star);
+                (numbers.len() == 2).then(|| format!(
+                    "line {}, column {}: gear {} * {} = {}",
+                    star.y + 1,
+                    star.x + 1,
+                    numbers[0],
+                    numbers[1],
+                    Answer::from(numbers[0]) * Answer::from(numbers[1])
+                ))
+            })
+            .collect())
+    }
 }
 
-pub fn part1() -> u32 {
-    let file = std::fs::File::open("problem_inputs/3.txt").unwrap();
-    let lines: Vec<String> = std::io::BufReader::new(file)
-        .lines()
-        .map(|line| line.unwrap())
-        .collect();
-    part1_result(lines)
+#[derive(Debug, PartialEq)]
+pub struct Schematic {
+    grid: Grid<char>
 }
 
-pub fn part2() -> u32 {
-    let file = std::fs::File::open("problem_inputs/3.txt").unwrap();
-    let lines: Vec<String> = std::io::BufReader::new(file)
-        .lines()
-        .map(|line| line.unwrap())
-        .collect();
-    part2_result(lines)
+impl std::fmt::Display for Schematic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
 }
 
-fn part1_result(lines: Vec<String>) -> u32 {
-    let mut result = 0;
-    let schematic = Schematic { lines };
-
-    for number in schematic.numbers() {
-        if is_next_to_symbol(
-            number.x,
-            number.y,
-            number.size,
-            &schematic
-        ) {
-            result += number.value;
-        }
+impl Schematic {
+    pub fn get(&self, point: Point) -> Option<char> {
+        self.grid.get(point).copied()
     }
 
-    result
-}
+    pub fn numbers(&self) -> Vec<NumberMatch> {
+        let mut result = Vec::new();
 
-fn is_next_to_symbol(
-    x: u32,
-    y: u32,
-    size: u32,
-    schematic: &Schematic
-) -> bool {
-    assert!(size > 0);
-
-    for x in x..(x + size) {
-        let neighbors: [(i32, i32); 8] = [
-            (-1, 1),
-            (0, 1),
-            (1, 1),
-            (-1, 0),
-            (1, 0),
-            (-1, -1),
-            (0, -1),
-            (1, -1)
-        ];
-
-        for (delta_x, delta_y) in neighbors {
-            let neighbor_x: i32 = x as i32 + delta_x;
-            let neighbor_y: i32 = y as i32 + delta_y;
-
-            if neighbor_x >= 0 && neighbor_y >= 0 {
-                let c = schematic.get(
-                    neighbor_x as u32,
-                    neighbor_y as u32
-                );
-
-                if let Some(c) = c {
-                    if is_symbol(c) {
-                        return true;
+        for (y, row) in self.grid.rows().enumerate() {
+            let mut current: Option<NumberMatch> = None;
+
+            for (x, c) in row.iter().enumerate() {
+                match (c.to_digit(10), current.as_mut()) {
+                    (Some(digit), Some(number)) => {
+                        number.value = number.value * 10 + digit;
+                        number.size += 1;
+                    }
+                    (Some(digit), None) => {
+                        current = Some(NumberMatch { start: Point::new(x, y), value: digit, size: 1 });
+                    }
+                    (None, _) => {
+                        result.extend(current.take());
                     }
                 }
             }
+
+            // A number can end the row.
+            result.extend(current);
         }
+
+        result
     }
 
-    false
-}
+    pub fn stars(&self) -> Vec<Point> {
+        self.grid.iter()
+            .filter(|&(_, &c)| c == '*')
+            .map(|(point, _)| point)
+            .collect()
+    }
 
-fn is_symbol(c: char) -> bool {
-    c != '.' && !c.is_ascii_digit()
-}
+    // Finds the numbers from the digits around the star, rather than going
+    // through every number of the schematic.
+    pub fn numbers_for_gear(&self, star: Point) -> Vec<u32> {
+        assert_eq!(self.get(star), Some('*'));
+        let is_digit = |point: &Point| self.grid[*point].is_ascii_digit();
+        let mut starts: Vec<Point> = self.grid.neighbors(star)
+            .filter(is_digit)
+            .map(|digit| self.grid.ray(digit, (-1, 0)).take_while(is_digit).last().unwrap())
+            .collect();
+        starts.sort_by_key(|start| (start.y, start.x));
+        starts.dedup();
+
+        starts.into_iter()
+            .map(|start| self.grid.ray(start, (1, 0))
+                 .take_while(is_digit)
+                 .fold(0, |number, point| number * 10 + self.grid[point].to_digit(10).unwrap()))
+            .collect()
+    }
 
-fn is_neighbor_of(x1: u32, y1: u32, x2: u32, y2: u32) -> bool {
-    let neighbor_deltas: [(i32, i32); 8] = [
-        (-1, 1),
-        (0, 1),
-        (1, 1),
-        (-1, 0),
-        (1, 0),
-        (-1, -1),
-        (0, -1),
-        (1, -1)
-    ];
+    pub fn is_part_number(&self, number: &NumberMatch) -> bool {
+        number.points()
+            .flat_map(|point| self.grid.neighbors(point))
+            .any(|neighbor| is_symbol(self.grid[neighbor]))
+    }
+}
 
-    let x1: i32 = x1.try_into().unwrap();
-    let y1: i32 = y1.try_into().unwrap();
-    let x2: i32 = x2.try_into().unwrap();
-    let y2: i32 = y2.try_into().unwrap();
-    neighbor_deltas.iter()
-        .any(|(delta_x, delta_y)| x1 + delta_x == x2 && y1 + delta_y == y2)
+#[derive(Debug)]
+pub struct NumberMatch {
+    pub start: Point,
+    pub value: u32,
+    pub size: usize
 }
 
-pub fn part2_result(lines: Vec<String>) -> u32 {
-    let mut result = 0;
-    let schematic = Schematic { lines };
+impl NumberMatch {
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (self.start.x..(self.start.x + self.size)).map(|x| Point::new(x, self.start.y))
+    }
+}
 
-    for star_match in schematic.stars() {
-        let numbers = schematic.numbers_for_gear(star_match.x, star_match.y);
+fn part1_result(schematic: &Schematic) -> Result<Answer, SolveError> {
+    let part_numbers = schematic.numbers()
+        .into_iter()
+        .filter(|number| schematic.is_part_number(number))
+        .map(|number| Some(Answer::from(number.value)));
+    solution::checked_sum(DAY, 1, "the sum of part numbers", part_numbers)
+}
 
-        if numbers.len() == 2 {
-            result += numbers.iter().product::<u32>();
-        }
-    }
+pub fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
 
-    result
+// A ratio of two u32 numbers always fits in an Answer, but the sum of the
+// ratios can still overflow.
+fn part2_result(schematic: &Schematic) -> Result<Answer, SolveError> {
+    let ratios = schematic.stars()
+        .into_iter()
+        .filter_map(|star| {
+            let numbers = schematic.numbers_for_gear(star);
+            (numbers.len() == 2).then(|| Some(Answer::from(numbers[0]) * Answer::from(numbers[1])))
+        });
+    solution::checked_sum(DAY, 2, "the sum of gear ratios", ratios)
 }
 
 #[test]
@@ -229,50 +193,37 @@ fn test_part1() {
         (vec!["123"], 0),
         (vec!["*123"], 123),
         (vec!["123*"], 123),
-        (vec!["1*1"], 2),
-        (vec![
-            "467..114..",
-            "...*......",
-            "..35..633.",
-            "......#...",
-            "617*......",
-            ".....+.58.",
-            "..592.....",
-            "......755.",
-            "...$.*....",
-            ".664.598.."
-        ],
-         4361)
+        (vec!["1*1"], 2)
     ];
 
     for (input, output) in data {
-        assert_eq!(
-            part1_result(
-                input.iter().map(|s| s.to_string()).collect()
-            ),
-            output
-        );
+        assert_eq!(Day3.part1(&Day3.parse(&input.join("\n")).unwrap()), Ok(output));
     }
 }
 
 #[test]
-fn test_part2() {
-    let input = vec![
-        "467..114..",
-        "...*......",
-        "..35..633.",
-        "......#...",
-        "617*......",
-        ".....+.58.",
-        "..592.....",
-        "......755.",
-        "...$.*....",
-        ".664.598.."
-    ];
+fn test_explain() {
+    let schematic = Day3.parse("467..114..\n...*......\n..35..633.").unwrap();
+    assert_eq!(
+        Solution::explain(&Day3, &schematic, 1),
+        Ok(vec![
+            "line 1, column 1: part number 467".to_owned(),
+            "line 3, column 3: part number 35".to_owned()
+        ])
+    );
     assert_eq!(
-        part2_result(
-            input.iter().map(|s| s.to_string()).collect()
-        ),
-        467835
+        Solution::explain(&Day3, &schematic, 2),
+        Ok(vec!["line 2, column 4: gear 467 * 35 = 16345".to_owned()])
     );
 }
+
+#[test]
+fn test_parsing_errors() {
+    let error = Day3.parse("467..114..\n...×......").err().unwrap();
+    assert_eq!((error.line, error.column, error.width), (2, 4, 1));
+    let error = Day3.parse("467..114..\n..*.99999999999.").err().unwrap();
+    assert_eq!((error.line, error.column, error.width), (2, 5, 11));
+    assert!(Day3.parse("..*.4294967295.").is_ok());
+    let error = Day3.parse("467..\n...").err().unwrap();
+    assert_eq!((error.line, error.message.as_str()), (2, "expected 5 cells"));
+}
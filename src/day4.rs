@@ -1,75 +1,145 @@
-use std::io::BufRead;
+use crate::counter::Counter;
+use crate::error::{LineError, ParseError, SolveError};
+use crate::parser;
+use crate::solution::{self, Answer, Solution, Trace};
 
-#[derive(Debug)]
-struct Card {
-    winning: Vec<u32>,
-    own: Vec<u32>
-}
+const DAY: u32 = 4;
 
-impl Card {
-    fn matching_cards(&self) -> u32 {
-        let mut result = 0u32;
+pub struct Day4;
 
-        for n in &self.own {
-            if self.winning.contains(n) {
-                result += 1;
-            }
+impl Solution for Day4 {
+    type Parsed = Vec<Card>;
+
+    fn day(&self) -> u32 {
+        DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Card>, ParseError> {
+        parser::lines(input.lines(), DAY, parse_card)
+    }
+
+    fn part1(&self, cards: &Vec<Card>) -> Result<Answer, SolveError> {
+        solution::checked_sum(DAY, 1, "the sum of points", cards.iter().map(Card::points))
+    }
+
+    fn part2(&self, cards: &Vec<Card>) -> Result<Answer, SolveError> {
+        part2_result(cards)
+    }
+
+    fn explain(&self, cards: &Vec<Card>, part: u32) -> Result<Trace, SolveError> {
+        if part == 1 {
+            return cards.iter()
+                .map(|card| Ok(format!(
+                    "card {}: matching numbers {}, points {}",
+                    card.id,
+                    card.matching_cards(),
+                    card.points().ok_or(SolveError::Overflow { day: DAY, part: 1, what: "points" })?
+                )))
+                .collect();
         }
 
-        result
+        let counts = card_counts(cards)?;
+        Ok(cards.iter()
+            .enumerate()
+            .map(|(i, card)| {
+                let won = &cards[i + 1..(i + 1 + card.matching_cards()).min(cards.len())];
+                let copies = match won {
+                    [] => "no copies".to_owned(),
+                    [won] => format!("a copy of card {}", won.id),
+                    [first, .., last] => format!("a copy of cards {} to {}", first.id, last.id)
+                };
+                format!(
+                    "card {}: {} in total, each winning {}",
+                    card.id,
+                    counts[i],
+                    copies
+                )
+            })
+            .collect())
     }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Card {
+    pub id: u32,
+    pub winning: Vec<u32>,
+    pub own: Vec<u32>
+}
+
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Card {}:", self.id)?;
+
+        for n in &self.winning {
+            write!(f, " {}", n)?;
+        }
 
-    fn points(&self) -> u32 {
-        let mut result = 0u32;
+        write!(f, " |")?;
 
         for n in &self.own {
-            if self.winning.contains(n) {
-                if result == 0 {
-                    result = 1;
-                } else {
-                    result *= 2;
-                }
-            }
+            write!(f, " {}", n)?;
         }
 
-        result
+        Ok(())
     }
 }
 
-pub fn part1() -> u32 {
-    let file = std::fs::File::open("problem_inputs/4.txt").unwrap();
-    let lines: Vec<String> = std::io::BufReader::new(file)
-        .lines()
-        .map(|line| line.unwrap())
-        .collect();
-    part1_result(lines)
+impl Card {
+    // The own numbers that are also winning ones, a number repeated on both
+    // sides matching as many times as it is on the side with fewer.
+    pub fn matching_numbers(&self) -> Counter<u32> {
+        let winning: Counter<u32> = self.winning.iter().copied().collect();
+        let own: Counter<u32> = self.own.iter().copied().collect();
+        own.intersection(&winning)
+    }
+
+    pub fn matching_cards(&self) -> usize {
+        self.matching_numbers().total()
+    }
+
+    // None when the points don't fit in an Answer.
+    pub fn points(&self) -> Option<Answer> {
+        match self.matching_cards() {
+            0 => Some(0),
+            n => u32::try_from(n - 1).ok().and_then(|shift| (1 as Answer).checked_shl(shift))
+        }
+    }
 }
 
-pub fn part2() -> u32 {
-    let file = std::fs::File::open("problem_inputs/4.txt").unwrap();
-    let lines: Vec<String> = std::io::BufReader::new(file)
-        .lines()
-        .map(|line| line.unwrap())
-        .collect();
-    part2_result(lines)
+pub fn part2_result(cards: &[Card]) -> Result<Answer, SolveError> {
+    let counts = card_counts(cards)?;
+    solution::checked_sum(DAY, 2, "the number of cards", counts.into_iter().map(Some))
 }
 
-fn part1_result(lines: Vec<String>) -> u32 {
-    let cards = parse_cards(lines);
-    cards.iter().map(|card| card.points()).sum()
+// Each card adds its count to the counts of the cards it wins copies of. The
+// counts can double with each card, so they overflow quickly.
+fn card_counts(cards: &[Card]) -> Result<Vec<Answer>, SolveError> {
+    let mut card_counts: Vec<Answer> = vec![1; cards.len()];
+
+    for (i, card) in cards.iter().enumerate() {
+        let reward = card.matching_cards().min(cards.len() - i - 1);
+
+        for j in (i + 1)..=(i + reward) {
+            card_counts[j] = card_counts[j].checked_add(card_counts[i])
+                .ok_or(SolveError::Overflow { day: DAY, part: 2, what: "the number of copies" })?;
+        }
+    }
+
+    Ok(card_counts)
 }
 
-fn part2_result(lines: Vec<String>) -> u32 {
-    let cards = parse_cards(lines);
-    let mut card_counts: Vec<u32> = vec![1; cards.len()];
+// Wins copies one at a time, as the puzzle describes it. Slow, but obviously
+// right, so it serves as a reference for part2_result.
+pub fn part2_copy_loop(cards: &[Card]) -> Answer {
+    let mut card_counts: Vec<Answer> = vec![1; cards.len()];
 
     for (i, card) in cards.iter().enumerate() {
-        let max_rewards: u32 = (cards.len() - i - 1).try_into().unwrap();
+        let max_rewards = cards.len() - i - 1;
         let reward = std::cmp::min(card.matching_cards(), max_rewards);
 
         for _ in 0..card_counts[i] {
             for j in 0..reward {
-                card_counts[i + ((j + 1) as usize)] += 1;
+                card_counts[i + j + 1] += 1;
             }
         }
     }
@@ -77,58 +147,70 @@ fn part2_result(lines: Vec<String>) -> u32 {
     card_counts.iter().sum()
 }
 
-fn parse_cards(lines: Vec<String>) -> Vec<Card> {
-    lines.iter().map(|line| parse_card(line)).collect()
-}
-
-fn parse_card(line: &str) -> Card {
-    let line: String = line.chars()
-        .skip_while(|&c| c != ':')
-        .skip(1)
-        .collect();
-    let x: Vec<&str> = line.split('|').collect();
-    assert_eq!(x.len(), 2);
-    let winning: Vec<u32> = x[0].split_ascii_whitespace()
-        .map(|x| x.parse::<u32>().unwrap())
-        .collect();
-    let own: Vec<u32> = x[1].split_ascii_whitespace()
-        .map(|x| x.parse::<u32>().unwrap())
-        .collect();
-    Card { winning, own }
+pub fn parse_card(line: &str) -> Result<Card, LineError> {
+    let (header, numbers) = parser::split_once(line, ":", "Card <id>: <numbers>")?;
+    let id = parser::label(header, "Card", "Card <id>")?.trim();
+    let id = parser::integer(id, "a card id")?;
+    let [winning, own] = parser::split_n(numbers, '|', "<winning> | <own>")?;
+    Ok(Card {
+        id,
+        winning: parser::integers(winning, "a number")?,
+        own: parser::integers(own, "a number")?
+    })
 }
 
 #[test]
-fn test_part1() {
+fn test_parsing_errors() {
     let data = [
-        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
-        "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
-        "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
-        "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
-        "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
-        "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
+        ("Card 1 41 48 | 83 86", 1, 20),
+        ("Crad 1: 41 48 | 83 86", 1, 6),
+        ("Card x: 41 48 | 83 86", 6, 1),
+        ("Card 1: 41 48 83 86", 8, 12),
+        ("Card 1: 41 48 | 83 | 86", 8, 16),
+        ("Card 1: 41 4b | 83 86", 12, 2)
     ];
+
+    for (line, column, width) in data {
+        let error = Day4.parse(line).err().unwrap();
+        assert_eq!((error.line, error.column, error.width), (1, column, width), "{}", line);
+    }
+}
+
+#[test]
+fn test_repeated_numbers() {
+    let cards = Day4.parse("Card 1: 5 | 5 5\nCard 2: 5 5 | 5\nCard 3: 5 5 | 5 5 6").unwrap();
+    let matches: Vec<usize> = cards.iter().map(Card::matching_cards).collect();
+    assert_eq!(matches, [1, 1, 2]);
+}
+
+#[test]
+fn test_explain() {
+    let cards = Day4.parse("Card 1: 1 2 | 1 2 3\nCard 2: 4 | 4\nCard 3: 5 | 6").unwrap();
+    assert_eq!(
+        Solution::explain(&Day4, &cards, 1).unwrap()[0],
+        "card 1: matching numbers 2, points 2"
+    );
     assert_eq!(
-        part1_result(
-            data.map(|line| line.to_owned()).to_vec()
-        ),
-        13
+        Solution::explain(&Day4, &cards, 2),
+        Ok(vec![
+            "card 1: 1 in total, each winning a copy of cards 2 to 3".to_owned(),
+            "card 2: 2 in total, each winning a copy of card 3".to_owned(),
+            "card 3: 4 in total, each winning no copies".to_owned()
+        ])
     );
 }
 
 #[test]
-fn test_part2() {
-    let data = [
-        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
-        "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
-        "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
-        "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
-        "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
-        "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
-    ];
+fn test_overflow() {
+    // Every card matches 70 numbers, so its count doubles the next one's.
+    let ones = vec!["1"; 70].join(" ");
+    let input: Vec<String> = (1..=70).map(|id| format!("Card {}: {} | {}", id, ones, ones)).collect();
+    let cards = Day4.parse(&input.join("\n")).unwrap();
+    assert_eq!(cards[0].points(), None);
+    assert_eq!(Day4.part1(&cards), Err(SolveError::Overflow { day: 4, part: 1, what: "the sum of points" }));
     assert_eq!(
-        part2_result(
-            data.map(|line| line.to_owned()).to_vec()
-        ),
-        30
+        Day4.part2(&cards),
+        Err(SolveError::Overflow { day: 4, part: 2, what: "the number of copies" })
     );
+    assert_eq!(part2_result(&cards[6..]), Ok(Answer::MAX));
 }
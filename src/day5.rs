@@ -1,27 +1,140 @@
-use std::io::BufRead;
+use crate::error::{self, LineError, ParseError, SolveError};
+use crate::interval::{Interval, RangeSet};
+use crate::parser::{self, Section};
+use crate::solution::{Answer, Solution, Trace};
 
-#[derive(Debug)]
-struct Almanac {
-    seeds: Vec<u32>,
-    maps: Vec<Map>
+const DAY: u32 = 5;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Parsed = Almanac;
+
+    fn day(&self) -> u32 {
+        DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Almanac, ParseError> {
+        let lines: Vec<&str> = input.lines().collect();
+        parse_almanac(&lines)
+    }
+
+    fn part1(&self, almanac: &Almanac) -> Result<Answer, SolveError> {
+        Ok(part1_result(almanac))
+    }
+
+    fn part2(&self, almanac: &Almanac) -> Result<Answer, SolveError> {
+        part2_result(almanac)
+    }
+
+    fn explain(&self, almanac: &Almanac, part: u32) -> Result<Trace, SolveError> {
+        if part == 1 {
+            return Ok(almanac.seeds.iter()
+                .map(|&seed| seed_path(seed.into(), almanac))
+                .collect());
+        }
+
+        Ok(seed_ranges(almanac)?.into_iter()
+            .map(|seeds| {
+                match lowest_location(almanac, seeds) {
+                    Some(location) => format!(
+                        "seeds {} to {}: lowest location {}",
+                        seeds.start,
+                        seeds.end - 1,
+                        location
+                    ),
+                    None => format!("seeds from {}: none", seeds.start)
+                }
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Almanac {
+    pub seeds: Vec<u32>,
+    pub maps: Vec<Map>
+}
+
+impl std::fmt::Display for Almanac {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "seeds:")?;
+
+        for seed in &self.seeds {
+            write!(f, " {}", seed)?;
+        }
+
+        writeln!(f)?;
+
+        for map in &self.maps {
+            write!(f, "\n{}", map)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Map {
+    pub from: Resource,
+    pub to: Resource,
+    pub ranges: Vec<MapRange>
 }
 
-#[derive(Debug)]
-struct Map {
-    from: Resource,
-    to: Resource,
-    ranges: Vec<MapRange>
+impl Map {
+    // Ids outside of every range map to themselves. Ids are wider than the
+    // input numbers, as a range can map them past u32::MAX.
+    // Parsing checks that the ranges don't overlap, so at most one contains
+    // the id.
+    pub fn lookup(&self, id: u64) -> u64 {
+        match self.ranges.iter().find(|range| range.contains(id)) {
+            Some(range) => id - range.source().start + range.destination().start,
+            None => id
+        }
+    }
 }
 
-#[derive(Debug, Copy, Clone)]
-struct MapRange {
-    destination_start: u32,
-    source_start: u32,
-    length: u32
+impl std::fmt::Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "{}-to-{} map:", self.from, self.to)?;
+
+        for range in &self.ranges {
+            writeln!(f, "{}", range)?;
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
-enum Resource {
+pub struct MapRange {
+    pub destination_start: u32,
+    pub source_start: u32,
+    pub length: u32
+}
+
+impl MapRange {
+    pub fn source(self) -> Interval {
+        Interval::with_length(self.source_start as u64, self.length as u64)
+    }
+
+    pub fn destination(self) -> Interval {
+        Interval::with_length(self.destination_start as u64, self.length as u64)
+    }
+
+    pub fn contains(self, source: u64) -> bool {
+        self.source().contains(source)
+    }
+}
+
+impl std::fmt::Display for MapRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.destination_start, self.source_start, self.length)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Resource {
     Seed,
     Soil,
     Fertilizer,
@@ -32,78 +145,111 @@ enum Resource {
     Location
 }
 
-pub fn part1() -> u32 {
-    let file = std::fs::File::open("problem_inputs/5.txt").unwrap();
-    let lines: Vec<String> = std::io::BufReader::new(file)
-        .lines()
-        .map(|line| line.unwrap())
-        .collect();
-    part1_result(&lines)
+impl std::fmt::Display for Resource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            Self::Seed => "seed",
+            Self::Soil => "soil",
+            Self::Fertilizer => "fertilizer",
+            Self::Water => "water",
+            Self::Light => "light",
+            Self::Temperature => "temperature",
+            Self::Humidity => "humidity",
+            Self::Location => "location"
+        };
+        write!(f, "{}", name)
+    }
 }
 
-fn part1_result<T: AsRef<str>>(lines: &[T]) -> u32 {
-    let almanac = parse_almanac(lines);
+fn part1_result(almanac: &Almanac) -> Answer {
     almanac.seeds.iter()
-        .map(|&seed| seed_location(seed, &almanac))
+        .map(|&seed| seed_location(seed.into(), almanac))
         .min()
         .unwrap()
 }
 
-pub fn part2() -> u32 {
-    let file = std::fs::File::open("problem_inputs/5.txt").unwrap();
-    let lines: Vec<String> = std::io::BufReader::new(file)
-        .lines()
-        .map(|line| line.unwrap())
-        .collect();
-    part2_result(&lines)
+// Part 2 reads the seeds as ranges, which can all be empty.
+const NO_SEEDS: SolveError = SolveError::Unsolvable { day: DAY, part: 2, reason: "every seed range is empty" };
+
+const UNPAIRED_SEEDS: SolveError = SolveError::Unsolvable {
+    day: DAY,
+    part: 2,
+    reason: "expected seeds in pairs of start and length"
+};
+
+// The seeds as part 2 reads them, in pairs of start and length.
+fn seed_ranges(almanac: &Almanac) -> Result<Vec<Interval>, SolveError> {
+    if !almanac.seeds.len().is_multiple_of(2) {
+        return Err(UNPAIRED_SEEDS);
+    }
+
+    Ok(almanac.seeds.chunks(2)
+        .map(|pair| Interval::with_length(pair[0].into(), pair[1].into()))
+        .collect())
 }
 
-fn part2_result<T: AsRef<str>>(lines: &[T]) -> u32 {
-    let almanac = parse_almanac(lines);
-    let mut result = u32::MAX;
+// Maps whole seed ranges through the almanac, splitting them wherever they
+// straddle the edge of a map range, so the work doesn't depend on how many
+// seeds there are.
+pub fn part2_result(almanac: &Almanac) -> Result<Answer, SolveError> {
+    seed_ranges(almanac)?.into_iter()
+        .filter_map(|seeds| lowest_location(almanac, seeds))
+        .min()
+        .ok_or(NO_SEEDS)
+}
 
-    for i in (0..almanac.seeds.len()).step_by(2) {
-        let range_start = almanac.seeds[i];
-        let range_length = almanac.seeds[i + 1];
-        let mut seed = range_start;
+// The lowest location of the seeds, if there are any.
+fn lowest_location(almanac: &Almanac, seeds: Interval) -> Option<u64> {
+    let mut ids: RangeSet = [seeds].into_iter().collect();
 
-        for _ in 0..range_length {
-            let location = seed_location(seed, &almanac);
+    for map in &almanac.maps {
+        ids = map_ids(map, &ids);
+    }
 
-            if location < result {
-                result = location;
-            }
+    ids.min()
+}
+
+// The ids after the map of all the ids before it.
+fn map_ids(map: &Map, ids: &RangeSet) -> RangeSet {
+    let sources: Vec<Interval> = map.ranges.iter().map(|range| range.source()).collect();
+    let mut result = Vec::new();
+
+    for &interval in ids.intervals() {
+        let (inside, outside) = interval.split(&sources);
+        result.extend(inside.into_iter().map(|(i, part)| {
+            part.shift(sources[i].start, map.ranges[i].destination().start)
+        }));
+        result.extend(outside);
+    }
+
+    result.into_iter().collect()
+}
+
+// Tries every seed, which takes minutes on a real input, but serves as a
+// reference for part2_result.
+pub fn part2_brute_force(almanac: &Almanac) -> Result<Answer, SolveError> {
+    let mut result = None;
 
-            seed += 1;
+    for seeds in seed_ranges(almanac)? {
+        for seed in seeds.start..seeds.end {
+            let location = seed_location(seed, almanac);
+
+            if result.is_none_or(|result| location < result) {
+                result = Some(location);
+            }
         }
     }
 
-    result
+    result.ok_or(NO_SEEDS)
 }
 
-fn seed_location(seed: u32, almanac: &Almanac) -> u32 {
+pub fn seed_location(seed: u64, almanac: &Almanac) -> u64 {
     let mut resource = Resource::Seed;
     let mut id = seed;
 
-    fn range_contains(range: MapRange, source: u32) -> bool {
-        let source_start = range.source_start as u64;
-        let length = range.length as u64;
-        let source = source as u64;
-        (source_start..(source_start + length)).contains(&source)
-    }
-
     for map in &almanac.maps {
         assert_eq!(map.from, resource);
-        let ranges: Vec<MapRange> = map.ranges.iter()
-            .filter(|&&range| range_contains(range, id))
-            .copied()
-            .collect();
-        assert!(ranges.len() <= 1, "{} {:?}", id, ranges);
-
-        if let Some(range) = ranges.first() {
-            id = range.destination_start + (id - range.source_start);
-        }
-
+        id = map.lookup(id);
         resource = map.to;
     }
 
@@ -111,165 +257,190 @@ fn seed_location(seed: u32, almanac: &Almanac) -> u32 {
     id
 }
 
-fn parse_almanac<T: AsRef<str>>(lines: &[T]) -> Almanac {
-    let seeds = parse_seeds(&lines[0]);
-    let maps = parse_maps(&lines[2..]);
-    Almanac { seeds, maps }
+// Every id the seed goes through, e.g. "seed 79 -> soil 81 -> ...".
+pub fn seed_path(seed: u64, almanac: &Almanac) -> String {
+    let mut id = seed;
+    let mut path = vec![format!("{} {}", Resource::Seed, id)];
+
+    for map in &almanac.maps {
+        id = map.lookup(id);
+        path.push(format!("{} {}", map.to, id));
+    }
+
+    path.join(" -> ")
+}
+
+pub fn parse_almanac(lines: &[&str]) -> Result<Almanac, ParseError> {
+    let first = lines.first()
+        .ok_or_else(|| error::missing(DAY, 0, "expected \"seeds: <ids>\""))?;
+    let seeds = parse_seeds(first).map_err(|error| error.locate(DAY, 0, first))?;
+    // The seeds line isn't blank, so it starts the first section.
+    let sections = parser::sections(lines.iter().copied());
+
+    if let Some(&line) = sections[0].lines.get(1) {
+        let error = LineError::whole(line, "expected a blank line");
+        return Err(error.locate(DAY, 1, line));
+    }
+
+    let maps = parse_maps(&sections[1..], lines.len())?;
+    Ok(Almanac { seeds, maps })
 }
 
-fn parse_seeds<T: AsRef<str>>(line: T) -> Vec<u32> {
-    line.as_ref()
-        .strip_prefix("seeds: ").unwrap()
+fn parse_seeds(line: &str) -> Result<Vec<u32>, LineError> {
+    let ids: Vec<&str> = parser::label(line, "seeds:", "seeds: <ids>")?
         .split_ascii_whitespace()
-        .map(|s| s.parse::<u32>().unwrap())
+        .collect();
+
+    if ids.is_empty() {
+        return Err(LineError::new(&line[line.len()..], "expected at least one seed"));
+    }
+
+    ids.iter()
+        .map(|id| parser::integer(id, "a seed id"))
         .collect()
 }
 
-fn parse_maps<T: AsRef<str>>(lines: &[T]) -> Vec<Map> {
+// Each section is a map, its first line naming the resources. `line_count` is
+// the number of lines in the whole input, for errors about missing maps.
+fn parse_maps(sections: &[Section], line_count: usize) -> Result<Vec<Map>, ParseError> {
     let mut result = vec![];
-    let mut resources: Option<(Resource, Resource)> = None;
-    let mut ranges: Vec<MapRange> = Vec::new();
-
-    for line in lines {
-        let line = line.as_ref();
-
-        if let Some((from, to)) = resources {
-            if line.is_empty() {
-                result.push(Map { from, to, ranges });
-                resources = None;
-                ranges = Vec::new();
-            } else {
-                ranges.push(parse_range(line));
+    let mut expected_from = Resource::Seed;
+
+    for section in sections {
+        // Only an empty section, between two blank lines, has no header.
+        let header = section.lines.first().copied().unwrap_or("");
+        let locate = |j: usize, line: &str, error: LineError| error.locate(DAY, section.start + j, line);
+        let (from, to) = parse_resources(header).map_err(|error| locate(0, header, error))?;
+
+        if from != expected_from {
+            let error = LineError::whole(
+                header,
+                format!("expected a map from {:?}", expected_from)
+            );
+            return Err(locate(0, header, error));
+        }
+
+        let mut ranges: Vec<MapRange> = Vec::new();
+
+        for (j, &line) in section.lines.iter().enumerate().skip(1) {
+            let range = parse_range(line).map_err(|error| locate(j, line, error))?;
+
+            if ranges.iter().any(|&other| range.source().overlaps(other.source())) {
+                return Err(locate(j, line, LineError::whole(line, "overlaps an earlier range")));
             }
-        } else {
-            let (from, to) = parse_resources(line);
-            resources = Some((from, to));
+
+            ranges.push(range);
         }
+
+        result.push(Map { from, to, ranges });
+        expected_from = to;
     }
 
-    if let Some((from, to)) = resources {
-        result.push(Map { from, to, ranges});
+    if expected_from != Resource::Location {
+        return Err(error::missing(
+            DAY,
+            line_count,
+            format!("expected a map from {:?}", expected_from)
+        ));
     }
 
-    result
+    Ok(result)
 }
 
-fn parse_resources(line: &str) -> (Resource, Resource) {
-    let parts: Vec<&str> = line.split_ascii_whitespace().collect();
-    assert_eq!(parts.len(), 2, "{:?}", parts);
-    assert_eq!(parts[1], "map:");
-    let parts: Vec<&str> = parts[0].split('-').collect();
-    assert_eq!(parts.len(), 3);
-    assert_eq!(parts[1], "to");
-    let from = parse_resource(parts[0]);
-    let to = parse_resource(parts[2]);
-    (from, to)
+fn parse_resources(line: &str) -> Result<(Resource, Resource), LineError> {
+    let expected = "<resource>-to-<resource> map:";
+    let invalid = || LineError::whole(line, format!("expected {:?}", expected));
+    let [name, "map:"] = parser::fields(line, expected)? else {
+        return Err(invalid());
+    };
+    let (from, to) = name.split_once("-to-").ok_or_else(invalid)?;
+    Ok((parse_resource(from)?, parse_resource(to)?))
 }
 
-fn parse_range(line: &str) -> MapRange {
-    let numbers: Vec<u32> = line.split_ascii_whitespace()
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect();
-    assert_eq!(numbers.len(), 3);
-    MapRange {
-        destination_start: numbers[0],
-        source_start: numbers[1],
-        length: numbers[2]
-    }
+fn parse_range(line: &str) -> Result<MapRange, LineError> {
+    let numbers: Vec<u32> = parser::integers(line, "a number")?;
+    let [destination_start, source_start, length] = numbers.try_into()
+        .map_err(|_| LineError::whole(
+            line,
+            "expected \"<destination start> <source start> <length>\""
+        ))?;
+    Ok(MapRange { destination_start, source_start, length })
 }
 
-fn parse_resource(s: &str) -> Resource {
-    match s {
-        "soil" => Resource::Soil,
-        "seed" => Resource::Seed,
-        "fertilizer" => Resource::Fertilizer,
-        "water" => Resource::Water,
-        "light" => Resource::Light,
-        "temperature" => Resource::Temperature,
-        "humidity" => Resource::Humidity,
-        "location" => Resource::Location,
-        _ => {
-            panic!("Unknown resource {:?}", s);
-        }
-    }
+fn parse_resource(s: &str) -> Result<Resource, LineError> {
+    let resources = [
+        ("seed", Resource::Seed),
+        ("soil", Resource::Soil),
+        ("fertilizer", Resource::Fertilizer),
+        ("water", Resource::Water),
+        ("light", Resource::Light),
+        ("temperature", Resource::Temperature),
+        ("humidity", Resource::Humidity),
+        ("location", Resource::Location)
+    ];
+    parser::one_of(s, &resources, "resource")
 }
 
 #[test]
-fn test_part1() {
-    let input = vec![
-        "seeds: 79 14 55 13",
-        "",
-        "seed-to-soil map:",
-        "50 98 2",
-        "52 50 48",
-        "",
-        "soil-to-fertilizer map:",
-        "0 15 37",
-        "37 52 2",
-        "39 0 15",
-        "",
-        "fertilizer-to-water map:",
-        "49 53 8",
-        "0 11 42",
-        "42 0 7",
-        "57 7 4",
-        "",
-        "water-to-light map:",
-        "88 18 7",
-        "18 25 70",
-        "",
-        "light-to-temperature map:",
-        "45 77 23",
-        "81 45 19",
-        "68 64 13",
-        "",
-        "temperature-to-humidity map:",
-        "0 69 1",
-        "1 0 69",
-        "",
-        "humidity-to-location map:",
-        "60 56 37",
-        "56 93 4",
+fn test_parsing_errors() {
+    let maps = "seed-to-location map:\n0 10 5";
+    let data = [
+        ("".to_owned(), 1, 1, 1),
+        ("seed: 1 2\n\n".to_owned() + maps, 1, 1, 9),
+        ("seeds:\n\n".to_owned() + maps, 1, 7, 0),
+        ("seeds: 1 x\n\n".to_owned() + maps, 1, 10, 1),
+        ("seeds: 1 2\nsoil\n".to_owned() + maps, 2, 1, 4),
+        ("seeds: 1 2\n\nseed-to-dirt map:\n0 10 5".to_owned(), 3, 9, 4),
+        ("seeds: 1 2\n\nseed-location map:\n0 10 5".to_owned(), 3, 1, 18),
+        ("seeds: 1 2\n\nsoil-to-location map:\n0 10 5".to_owned(), 3, 1, 21),
+        ("seeds: 1 2\n\n".to_owned() + maps + "\n5 12 1", 5, 1, 6),
+        ("seeds: 1 2\n\n".to_owned() + maps + " 3", 4, 1, 8),
+        ("seeds: 1 2\n\nseed-to-soil map:\n0 10 5".to_owned(), 5, 1, 1)
     ];
-    assert_eq!(part1_result(&input), 35);
+
+    for (input, line, column, width) in data {
+        let error = Day5.parse(&input).err().unwrap();
+        assert_eq!(
+            (error.line, error.column, error.width),
+            (line, column, width),
+            "{}",
+            error
+        );
+    }
 }
 
 #[test]
-fn test_part2() {
-    let input = vec![
-        "seeds: 79 14 55 13",
-        "",
-        "seed-to-soil map:",
-        "50 98 2",
-        "52 50 48",
-        "",
-        "soil-to-fertilizer map:",
-        "0 15 37",
-        "37 52 2",
-        "39 0 15",
-        "",
-        "fertilizer-to-water map:",
-        "49 53 8",
-        "0 11 42",
-        "42 0 7",
-        "57 7 4",
-        "",
-        "water-to-light map:",
-        "88 18 7",
-        "18 25 70",
-        "",
-        "light-to-temperature map:",
-        "45 77 23",
-        "81 45 19",
-        "68 64 13",
-        "",
-        "temperature-to-humidity map:",
-        "0 69 1",
-        "1 0 69",
-        "",
-        "humidity-to-location map:",
-        "60 56 37",
-        "56 93 4",
-    ];
-    assert_eq!(part2_result(&input), 46);
+fn test_unpaired_seeds() {
+    let almanac = Day5.parse("seeds: 10 5 60\n\nseed-to-location map:\n50 12 3").unwrap();
+    assert_eq!(Day5.part1(&almanac), Ok(5));
+    assert_eq!(part2_result(&almanac), Err(UNPAIRED_SEEDS));
+    assert_eq!(part2_brute_force(&almanac), Err(UNPAIRED_SEEDS));
+    assert_eq!(Solution::explain(&Day5, &almanac, 2), Err(UNPAIRED_SEEDS));
+}
+
+#[test]
+fn test_explain() {
+    let almanac = Day5.parse("seeds: 10 5 60 0\n\nseed-to-location map:\n50 12 3").unwrap();
+    assert_eq!(
+        Solution::explain(&Day5, &almanac, 1),
+        Ok(vec![
+            "seed 10 -> location 10".to_owned(),
+            "seed 5 -> location 5".to_owned(),
+            "seed 60 -> location 60".to_owned(),
+            "seed 0 -> location 0".to_owned()
+        ])
+    );
+    assert_eq!(
+        Solution::explain(&Day5, &almanac, 2),
+        Ok(vec!["seeds 10 to 14: lowest location 10".to_owned(), "seeds from 60: none".to_owned()])
+    );
+    assert_eq!(seed_path(13, &almanac), "seed 13 -> location 51");
+}
+
+#[test]
+fn test_empty_seed_ranges() {
+    let almanac = Day5.parse("seeds: 10 0 60 0\n\nseed-to-location map:\n50 12 3").unwrap();
+    assert_eq!(Day5.part1(&almanac), Ok(0));
+    assert_eq!(part2_result(&almanac), Err(NO_SEEDS));
+    assert_eq!(part2_brute_force(&almanac), Err(NO_SEEDS));
 }
@@ -1,101 +1,230 @@
-use std::io::BufRead;
+use crate::error::{LineError, ParseError, SolveError};
+use crate::parser;
+use crate::solution::{Answer, Solution, Trace};
 
-#[derive(Copy, Clone)]
-struct Race {
-    duration: u64,
-    best_distance: u64
+const DAY: u32 = 6;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Parsed = Sheet;
+
+    fn day(&self) -> u32 {
+        DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Sheet, ParseError> {
+        let lines = parser::key_values(input, DAY, &["Time:", "Distance:"], "<numbers>")?;
+        let mut separate = Vec::new();
+        let mut kerned = Vec::new();
+
+        for (i, &(line, numbers)) in lines.iter().enumerate() {
+            let locate = |error: LineError| error.locate(DAY, i, line);
+            separate.push(parser::integers::<u64>(numbers, "a number").map_err(locate)?);
+            kerned.push(parse_kerned(numbers).map_err(locate)?);
+        }
+
+        if separate[0].len() != separate[1].len() {
+            let line = lines[1].0;
+            let error = LineError::whole(
+                line,
+                format!("expected {} distances", separate[0].len())
+            );
+            return Err(error.locate(DAY, 1, line));
+        }
+
+        Ok(Sheet {
+            races: separate[0].iter()
+                .zip(&separate[1])
+                .map(|(&duration, &best_distance)| Race { duration, best_distance })
+                .collect(),
+            kerned_race: Race {
+                duration: kerned[0],
+                best_distance: kerned[1]
+            }
+        })
+    }
+
+    fn part1(&self, sheet: &Sheet) -> Result<Answer, SolveError> {
+        // Each race has fewer ways to win than its duration, whose digits all
+        // make it into the kerned duration, so the product fits as long as that
+        // parsed. It is still checked, since a Sheet can be built without parsing.
+        let mut result: Answer = 1;
+
+        for &race in &sheet.races {
+            result = result.checked_mul(win_possibilities(race))
+                .ok_or(SolveError::Overflow { day: DAY, part: 1, what: "the product of ways to win" })?;
+        }
+
+        Ok(result)
+    }
+
+    fn part2(&self, sheet: &Sheet) -> Result<Answer, SolveError> {
+        Ok(win_possibilities(sheet.kerned_race))
+    }
+
+    fn explain(&self, sheet: &Sheet, part: u32) -> Result<Trace, SolveError> {
+        let races = if part == 1 { sheet.races.clone() } else { vec![sheet.kerned_race] };
+        Ok(races.into_iter()
+            .enumerate()
+            .map(|(i, race)| {
+                let holds = match winning_holds(race) {
+                    Some(holds) => format!("holding {} to {} ms wins", holds.start(), holds.end()),
+                    None => "no hold wins".to_owned()
+                };
+                format!(
+                    "race {}: {} ms, record {} mm, {}, {} ways",
+                    i + 1,
+                    race.duration,
+                    race.best_distance,
+                    holds,
+                    win_possibilities(race)
+                )
+            })
+            .collect())
+    }
 }
 
-pub fn part1() -> u32 {
-    let file = std::fs::File::open("problem_inputs/6.txt").unwrap();
-    let lines: Vec<String> = std::io::BufReader::new(file)
-        .lines()
-        .map(|line| line.unwrap())
-        .collect();
-    part1_result(&lines)
+// The same sheet read both ways: as separate races for part 1, and as a
+// single race with the spaces between digits ignored for part 2.
+#[derive(Debug, PartialEq)]
+pub struct Sheet {
+    pub races: Vec<Race>,
+    pub kerned_race: Race
 }
 
-pub fn part2() -> u32 {
-    let file = std::fs::File::open("problem_inputs/6.txt").unwrap();
-    let lines: Vec<String> = std::io::BufReader::new(file)
-        .lines()
-        .map(|line| line.unwrap())
-        .collect();
-    part2_result(&lines)
+// Only the separate races are printed, since reading them back gives the
+// kerned race again.
+impl std::fmt::Display for Sheet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Time:")?;
+
+        for race in &self.races {
+            write!(f, " {}", race.duration)?;
+        }
+
+        write!(f, "\nDistance:")?;
+
+        for race in &self.races {
+            write!(f, " {}", race.best_distance)?;
+        }
+
+        writeln!(f)
+    }
 }
 
-fn part1_result<T: AsRef<str>>(lines: &[T]) -> u32 {
-    let lines: Vec<_> = lines.iter()
-        .map(|line| part1_parse_line(line.as_ref()))
-        .collect();
-    assert_eq!(lines.len(), 2);
-    lines[0].iter()
-        .zip(&lines[1])
-        .map(|(&duration, &best_distance)| Race { duration, best_distance })
-        .map(win_possibilities)
-        .product()
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Race {
+    pub duration: u64,
+    pub best_distance: u64
+}
+
+// Holding for h wins when h * (duration - h) > best_distance. The winning
+// holds are the integers strictly between the roots of that quadratic, and
+// are symmetric around duration / 2.
+pub fn winning_holds(race: Race) -> Option<std::ops::RangeInclusive<u64>> {
+    let duration = race.duration as u128;
+    let wins = |hold: u128| hold * (duration - hold) > race.best_distance as u128;
+
+    if !wins(duration / 2) {
+        return None;
+    }
+
+    let root = (duration * duration - 4 * race.best_distance as u128).isqrt();
+    // The square root is rounded down, so the first win is at most a step
+    // away from this estimate.
+    let mut first = (duration - root) / 2;
+
+    while !wins(first) {
+        first += 1;
+    }
+
+    while first > 0 && wins(first - 1) {
+        first -= 1;
+    }
+
+    Some(first as u64..=(duration - first) as u64)
 }
 
-fn part1_parse_line(s: &str) -> Vec<u64> {
-    let i = s.find(':').unwrap();
-    let s = &s[(i + 1)..];
-    s.trim()
-        .split_ascii_whitespace()
-        .map(|n| n.parse::<u64>().unwrap())
-        .collect()
+// Holding for 0 never wins, so the count always fits.
+pub fn win_possibilities(race: Race) -> Answer {
+    winning_holds(race)
+        .map(|holds| holds.end() - holds.start() + 1)
+        .unwrap_or(0)
 }
 
-fn win_possibilities(race: Race) -> u32 {
+// Tries every hold duration. Too slow for long races, but serves as a
+// reference for win_possibilities.
+pub fn win_possibilities_scan(race: Race) -> Answer {
     (1..(race.duration))
         .filter(|&pressed_duration| leads_to_win(pressed_duration, race))
-        .count()
-        .try_into()
-        .unwrap()
+        .count() as Answer
 }
 
-fn leads_to_win(pressed_duration: u64, race: Race) -> bool {
+pub fn leads_to_win(pressed_duration: u64, race: Race) -> bool {
     assert_ne!(pressed_duration, 0);
     assert_ne!(pressed_duration, race.duration);
-    let speed = pressed_duration;
-    let distance = speed * (race.duration - pressed_duration);
-    distance > race.best_distance
+    let speed = pressed_duration as u128;
+    let distance = speed * (race.duration - pressed_duration) as u128;
+    distance > race.best_distance as u128
 }
 
-fn part2_result<T: AsRef<str>>(lines: &[T]) -> u32 {
-    let numbers: Vec<_> = lines.iter()
-        .map(|line| part2_parse_line(line.as_ref()))
-        .collect();
-    assert_eq!(numbers.len(), 2);
-    let race = Race {
-        duration: numbers[0],
-        best_distance: numbers[1]
-    };
-    win_possibilities(race)
-}
-
-fn part2_parse_line(s: &str) -> u64 {
-    let i = s.find(':').unwrap();
-    let s = s[(i + 1)..].trim();
-    let s: String = s.chars()
+// The numbers read as one, ignoring the spaces between them.
+fn parse_kerned(numbers: &str) -> Result<u64, LineError> {
+    let numbers = numbers.trim();
+    let digits: String = numbers.chars()
         .filter(|c| !c.is_ascii_whitespace())
         .collect();
-    s.parse().unwrap()
+    digits.parse()
+        .map_err(|_| LineError::new(numbers, "expected a number"))
 }
 
 #[test]
-fn test_part1() {
-    let input = vec![
-        "Time:      7  15   30",
-        "Distance:  9  40  200"
+fn test_parsing_errors() {
+    let data = [
+        ("Time: 7 15", 2, 1, 1),
+        ("Time: 7 15\nDistance: 9 40\n\n", 3, 1, 0),
+        ("Tiem: 7 15\nDistance: 9 40", 1, 1, 10),
+        ("Time: 7 l5\nDistance: 9 40", 1, 9, 2),
+        ("Time: 7 15\nDistance: 9", 2, 1, 11),
+        ("Time: 99999999999 99999999999\nDistance: 9 40", 1, 7, 23)
     ];
-    assert_eq!(part1_result(&input), 288);
+
+    for (input, line, column, width) in data {
+        let error = Day6.parse(input).err().unwrap();
+        assert_eq!(
+            (error.line, error.column, error.width),
+            (line, column, width),
+            "{}",
+            error
+        );
+    }
 }
 
 #[test]
-fn test_part2() {
-    let input = vec![
-        "Time:      7  15   30",
-        "Distance:  9  40  200"
-    ];
-    assert_eq!(part2_result(&input), 71503);
+fn test_explain() {
+    let sheet = Day6.parse("Time: 7 15\nDistance: 9 40").unwrap();
+    assert_eq!(
+        Solution::explain(&Day6, &sheet, 1),
+        Ok(vec![
+            "race 1: 7 ms, record 9 mm, holding 2 to 5 ms wins, 4 ways".to_owned(),
+            "race 2: 15 ms, record 40 mm, holding 4 to 11 ms wins, 8 ways".to_owned()
+        ])
+    );
+    assert_eq!(
+        Solution::explain(&Day6, &sheet, 2),
+        Ok(vec!["race 1: 715 ms, record 940 mm, holding 2 to 713 ms wins, 712 ways".to_owned()])
+    );
+    assert_eq!(winning_holds(Race { duration: 4, best_distance: 4 }), None);
+}
+
+#[test]
+fn test_overflow() {
+    let race = Race { duration: u64::MAX, best_distance: 0 };
+    assert_eq!(win_possibilities(race), u64::MAX - 1);
+    let sheet = Sheet { races: vec![race, race], kerned_race: race };
+    assert_eq!(
+        Day6.part1(&sheet),
+        Err(SolveError::Overflow { day: 6, part: 1, what: "the product of ways to win" })
+    );
 }
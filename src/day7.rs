@@ -0,0 +1,111 @@
+use crate::day7_part1;
+use crate::day7_part2;
+use crate::error::{ParseError, SolveError};
+use crate::solution::{Answer, Solution, Trace};
+
+const DAY: u32 = 7;
+
+pub struct Day7;
+
+// J is a jack in part 1 and a joker in part 2, so each part keeps its own
+// reading of the bids.
+#[derive(Debug, PartialEq)]
+pub struct Bids {
+    pub part1: Vec<day7_part1::Bid>,
+    pub part2: Vec<day7_part2::Bid>
+}
+
+// Both readings come from the same lines, so printing either gives them back.
+impl std::fmt::Display for Bids {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for bid in &self.part1 {
+            writeln!(f, "{}", bid)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Solution for Day7 {
+    type Parsed = Bids;
+
+    fn day(&self) -> u32 {
+        DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Bids, ParseError> {
+        let lines: Vec<&str> = input.lines().collect();
+        Ok(Bids {
+            part1: day7_part1::parse(&lines)?,
+            part2: day7_part2::parse(&lines)?
+        })
+    }
+
+    fn part1(&self, bids: &Bids) -> Result<Answer, SolveError> {
+        day7_part1::winnings(&bids.part1)
+    }
+
+    fn part2(&self, bids: &Bids) -> Result<Answer, SolveError> {
+        day7_part2::part2_result(&bids.part2)
+    }
+
+    // Bids from the weakest hand to the strongest, as winnings sorts them.
+    fn explain(&self, bids: &Bids, part: u32) -> Result<Trace, SolveError> {
+        if part == 1 {
+            let mut bids = bids.part1.clone();
+            bids.sort_by(|a, b| day7_part1::compare_hands(&a.cards, &b.cards));
+            return Ok(bids.iter()
+                .enumerate()
+                .map(|(i, bid)| format!(
+                    "rank {}: {} is {:?}, wins {} * {} = {}",
+                    i + 1,
+                    hand(&bid.cards),
+                    day7_part1::hand_type(&bid.cards),
+                    bid.value,
+                    i + 1,
+                    Answer::from(bid.value) * (i as Answer + 1)
+                ))
+                .collect());
+        }
+
+        let fixed_up = day7_part2::replace_jokers(&bids.part2);
+        let mut order: Vec<usize> = (0..fixed_up.len()).collect();
+        order.sort_by(|&a, &b| day7_part2::compare_hands(&fixed_up[a].cards, &fixed_up[b].cards));
+        Ok(order.iter()
+            .enumerate()
+            .map(|(i, &j)| format!(
+                "rank {}: {} plays as {}, {:?}, wins {} * {} = {}",
+                i + 1,
+                hand(&bids.part2[j].cards),
+                hand(&fixed_up[j].cards),
+                day7_part2::hand_type(&fixed_up[j].cards),
+                fixed_up[j].value,
+                i + 1,
+                Answer::from(fixed_up[j].value) * (i as Answer + 1)
+            ))
+            .collect())
+    }
+}
+
+fn hand(cards: &[impl std::fmt::Display]) -> String {
+    cards.iter().map(|card| card.to_string()).collect()
+}
+
+#[test]
+fn test_explain() {
+    let bids = Day7.parse("KTJJT 220\n32T3K 765").unwrap();
+    assert_eq!(
+        Solution::explain(&Day7, &bids, 1),
+        Ok(vec![
+            "rank 1: 32T3K is OnePair, wins 765 * 1 = 765".to_owned(),
+            "rank 2: KTJJT is TwoPair, wins 220 * 2 = 440".to_owned()
+        ])
+    );
+    assert_eq!(
+        Solution::explain(&Day7, &bids, 2),
+        Ok(vec![
+            "rank 1: 32T3K plays as 32T3K, OnePair, wins 765 * 1 = 765".to_owned(),
+            "rank 2: KTJJT plays as KTTTT, FourOfAKind, wins 220 * 2 = 440".to_owned()
+        ])
+    );
+}
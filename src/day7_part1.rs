@@ -1,26 +1,55 @@
-use std::io::BufRead;
+use crate::counter::Counter;
+use crate::error::{LineError, ParseError, SolveError};
+use crate::parser;
+use crate::solution::{self, Answer};
 
-#[derive(Copy, Clone, Debug)]
-enum Card {
+const DAY: u32 = 7;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Card {
     Value(u32)
 }
 
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let c = match self.require_value() {
+            10 => 'T',
+            11 => 'J',
+            12 => 'Q',
+            13 => 'K',
+            14 => 'A',
+            n => char::from_digit(n, 10).unwrap()
+        };
+        write!(f, "{}", c)
+    }
+}
+
 impl Card {
-    fn require_value(self) -> u32 {
+    pub fn require_value(self) -> u32 {
         match self {
             Self::Value(n) => n
         }
     }
 }
 
-#[derive(Debug)]
-struct Bid {
-    cards: Vec<Card>,
-    value: u32
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bid {
+    pub cards: Vec<Card>,
+    pub value: u32
+}
+
+impl std::fmt::Display for Bid {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for card in &self.cards {
+            write!(f, "{}", card)?;
+        }
+
+        write!(f, " {}", self.value)
+    }
 }
 
-#[derive(Copy, Clone)]
-enum HandType {
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HandType {
     FiveOfAKind = 7,
     FourOfAKind = 6,
     FullHouse = 5,
@@ -30,38 +59,28 @@ enum HandType {
     HighCard = 1
 }
 
-pub fn part1() -> u32 {
-    let file = std::fs::File::open("problem_inputs/7.txt").unwrap();
-    let lines: Vec<String> = std::io::BufReader::new(file)
-        .lines()
-        .map(|line| line.unwrap())
-        .collect();
-    part1_result(&lines)
+pub fn parse(lines: &[impl AsRef<str>]) -> Result<Vec<Bid>, ParseError> {
+    parser::lines(lines.iter().map(AsRef::as_ref), DAY, parse_line)
 }
 
-fn part1_result(lines: &[impl AsRef<str>]) -> u32 {
-    let mut bids = parse(lines);
-    winnings(&mut bids)
-}
+fn parse_line(line: &str) -> Result<Bid, LineError> {
+    let [hand, value] = parser::fields(line, "<hand> <bid>")?;
+    let cards: Vec<Card> = hand.char_indices()
+        .map(|(i, c)| parse_card(c).ok_or_else(|| {
+            LineError::new(&hand[i..(i + c.len_utf8())], format!("unknown card {:?}", c))
+        }))
+        .collect::<Result<_, _>>()?;
 
-fn parse(lines: &[impl AsRef<str>]) -> Vec<Bid> {
-    lines.iter()
-        .map(|line| parse_line(line.as_ref()))
-        .collect()
-}
+    if cards.len() != 5 {
+        return Err(LineError::new(hand, "expected 5 cards"));
+    }
 
-fn parse_line(line: &str) -> Bid {
-    let segments: Vec<_> = line.split_ascii_whitespace().collect();
-    assert_eq!(segments.len(), 2);
-    let cards: Vec<Card> = segments[0].chars()
-        .map(parse_card)
-        .collect();
-    let value = segments[1].parse::<u32>().unwrap();
-    Bid { cards, value }
+    let value = parser::integer(value, "a bid")?;
+    Ok(Bid { cards, value })
 }
 
-fn parse_card(c: char) -> Card {
-    let value = if let Some(n) = c.to_digit(10) {
+fn parse_card(c: char) -> Option<Card> {
+    let value = if let Some(n) = c.to_digit(10).filter(|n| (2..=9).contains(n)) {
         n
     } else {
         match c {
@@ -71,23 +90,24 @@ fn parse_card(c: char) -> Card {
             'K' => 13,
             'A' => 14,
             _ => {
-                unreachable!("Unknown card {}", c);
+                return None;
             }
         }
     };
 
-    Card::Value(value)
+    Some(Card::Value(value))
 }
 
-fn winnings(bids: &mut [Bid]) -> u32 {
-    bids.sort_by(|a, b| compare_hands(&a.cards, &b.cards));
-    bids.iter()
+pub fn winnings(bids: &[Bid]) -> Result<Answer, SolveError> {
+    let mut ranked: Vec<&Bid> = bids.iter().collect();
+    ranked.sort_by(|a, b| compare_hands(&a.cards, &b.cards));
+    let values = ranked.iter()
         .enumerate()
-        .map(|(i, bid)| bid.value * (i as u32 + 1))
-        .sum()
+        .map(|(i, bid)| Answer::from(bid.value).checked_mul(i as Answer + 1));
+    solution::checked_sum(DAY, 1, "the total winnings", values)
 }
 
-fn compare_hands(a: &[Card], b: &[Card]) -> std::cmp::Ordering {
+pub fn compare_hands(a: &[Card], b: &[Card]) -> std::cmp::Ordering {
     let hand_a = hand_type(a) as u8;
     let hand_b = hand_type(b) as u8;
 
@@ -103,47 +123,34 @@ fn compare_hands(a: &[Card], b: &[Card]) -> std::cmp::Ordering {
     }
 }
 
-fn hand_type(cards: &[Card]) -> HandType {
-    let mut occurrences = std::collections::HashMap::<u32, u8>::new();
-
-    for &card in cards {
-        let key = card.require_value();
-
-        if let Some(n) = occurrences.get(&key) {
-            occurrences.insert(key, n + 1);
-        } else {
-            occurrences.insert(key, 1);
-        }
-    }
-
-    let values: Vec<u8> = occurrences.values().copied().collect();
-
-    if values.contains(&5) {
-        HandType::FiveOfAKind
-    } else if values.contains(&4) {
-        HandType::FourOfAKind
-    } else if values.contains(&3) && values.contains(&2) {
-        HandType::FullHouse
-    } else if values.contains(&3) {
-        HandType::ThreeOfAKind
-    } else if values.contains(&2) && values.len() == 3 {
-        HandType::TwoPair
-    } else if values.contains(&2) {
-        HandType::OnePair
-    } else {
-        assert!(values.iter().all(|&v| v == 1), "{:?}", values);
-        HandType::HighCard
+pub fn hand_type(cards: &[Card]) -> HandType {
+    let counter: Counter<u32> = cards.iter().map(|card| card.require_value()).collect();
+
+    match counter.signature().as_slice() {
+        [5] => HandType::FiveOfAKind,
+        [4, 1] => HandType::FourOfAKind,
+        [3, 2] => HandType::FullHouse,
+        [3, 1, 1] => HandType::ThreeOfAKind,
+        [2, 2, 1] => HandType::TwoPair,
+        [2, 1, 1, 1] => HandType::OnePair,
+        [1, 1, 1, 1, 1] => HandType::HighCard,
+        signature => panic!("not a hand of 5 cards: {:?}", signature)
     }
 }
 
 #[test]
-fn test_part1() {
-    let input = vec![
-        "32T3K 765",
-        "T55J5 684",
-        "KK677 28",
-        "KTJJT 220",
-        "QQQJA 483"
+fn test_parsing_errors() {
+    let data = [
+        ("32T3K", 1, 5),
+        ("32T3K 765 1", 1, 11),
+        ("32T1K 765", 4, 1),
+        ("32T3 765", 1, 4),
+        ("32T3KK 765", 1, 6),
+        ("32T3K -765", 7, 4)
     ];
-    assert_eq!(part1_result(&input), 6440);
+
+    for (line, column, width) in data {
+        let error = parse(&[line]).err().unwrap();
+        assert_eq!((error.line, error.column, error.width), (1, column, width), "{}", line);
+    }
 }
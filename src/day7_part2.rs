@@ -1,7 +1,12 @@
-use std::io::BufRead;
+use crate::counter::Counter;
+use crate::error::{LineError, ParseError, SolveError};
+use crate::parser;
+use crate::solution::{self, Answer};
+
+const DAY: u32 = 7;
 
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
-enum CardValue {
+pub enum CardValue {
     A,
     K,
     Q,
@@ -11,7 +16,7 @@ enum CardValue {
 }
 
 impl CardValue {
-    fn value(self) -> u32 {
+    pub fn value(self) -> u32 {
         match self {
             Self::A => 14,
             Self::K => 13,
@@ -37,7 +42,7 @@ impl std::fmt::Display for CardValue {
 }
 
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
-enum InitialCard {
+pub enum InitialCard {
     Value(CardValue),
     Joker
 }
@@ -52,13 +57,13 @@ impl std::fmt::Display for InitialCard {
 }
 
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
-enum FixedUpCard {
+pub enum FixedUpCard {
     Regular { value: CardValue },
     Joker { replaced_with: CardValue }
 }
 
 impl FixedUpCard {
-    fn value(self) -> u32 {
+    pub fn value(self) -> u32 {
         match self {
             Self::Regular { value } => value.value(),
             Self::Joker { replaced_with: _ } => 1
@@ -81,10 +86,10 @@ impl std::fmt::Display for FixedUpCard {
     }
 }
 
-#[derive(Debug)]
-struct Bid {
-    cards: Vec<InitialCard>,
-    value: u32
+#[derive(Debug, PartialEq)]
+pub struct Bid {
+    pub cards: Vec<InitialCard>,
+    pub value: u32
 }
 
 impl std::fmt::Display for Bid {
@@ -98,9 +103,9 @@ impl std::fmt::Display for Bid {
 }
 
 #[derive(Debug)]
-struct FixedUpBid {
-    cards: Vec<FixedUpCard>,
-    value: u32
+pub struct FixedUpBid {
+    pub cards: Vec<FixedUpCard>,
+    pub value: u32
 }
 
 impl std::fmt::Display for FixedUpBid {
@@ -114,8 +119,8 @@ impl std::fmt::Display for FixedUpBid {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-enum HandType {
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HandType {
     FiveOfAKind = 7,
     FourOfAKind = 6,
     FullHouse = 5,
@@ -125,63 +130,55 @@ enum HandType {
     HighCard = 1
 }
 
-pub fn part2() -> u32 {
-    let file = std::fs::File::open("problem_inputs/7.txt").unwrap();
-    let lines: Vec<String> = std::io::BufReader::new(file)
-        .lines()
-        .map(|line| line.unwrap())
-        .collect();
-    part2_result(&lines)
+pub fn part2_result(bids_with_jokers: &[Bid]) -> Result<Answer, SolveError> {
+    winnings(&replace_jokers(bids_with_jokers))
 }
 
-fn part2_result(lines: &[impl AsRef<str>]) -> u32 {
-    let bids_with_jokers = parse(lines);
-    let mut bids = replace_jokers(&bids_with_jokers);
-    winnings(&mut bids)
+pub fn parse(lines: &[impl AsRef<str>]) -> Result<Vec<Bid>, ParseError> {
+    parser::lines(lines.iter().map(AsRef::as_ref), DAY, parse_line)
 }
 
-fn parse(lines: &[impl AsRef<str>]) -> Vec<Bid> {
-    lines.iter()
-        .map(|line| parse_line(line.as_ref()))
-        .collect()
-}
+fn parse_line(line: &str) -> Result<Bid, LineError> {
+    let [hand, value] = parser::fields(line, "<hand> <bid>")?;
+    let cards: Vec<InitialCard> = hand.char_indices()
+        .map(|(i, c)| parse_card(c).ok_or_else(|| {
+            LineError::new(&hand[i..(i + c.len_utf8())], format!("unknown card {:?}", c))
+        }))
+        .collect::<Result<_, _>>()?;
 
-fn parse_line(line: &str) -> Bid {
-    let segments: Vec<_> = line.split_ascii_whitespace().collect();
-    assert_eq!(segments.len(), 2);
-    let cards: Vec<InitialCard> = segments[0].chars()
-        .map(parse_card)
-        .collect();
-    let value = segments[1].parse::<u32>().unwrap();
-    Bid { cards, value }
+    if cards.len() != 5 {
+        return Err(LineError::new(hand, "expected 5 cards"));
+    }
+
+    let value = parser::integer(value, "a bid")?;
+    Ok(Bid { cards, value })
 }
 
-fn parse_card(c: char) -> InitialCard {
-    if let Some(n) = c.to_digit(10) {
-        InitialCard::Value(CardValue::Number(n))
+fn parse_card(c: char) -> Option<InitialCard> {
+    if let Some(n) = c.to_digit(10).filter(|n| (2..=9).contains(n)) {
+        Some(InitialCard::Value(CardValue::Number(n)))
     } else {
         match c {
-            'T' => InitialCard::Value(CardValue::T),
-            'J' => InitialCard::Joker,
-            'Q' => InitialCard::Value(CardValue::Q),
-            'K' => InitialCard::Value(CardValue::K),
-            'A' => InitialCard::Value(CardValue::A),
-            _ => {
-                unreachable!("Unknown card {}", c);
-            }
+            'T' => Some(InitialCard::Value(CardValue::T)),
+            'J' => Some(InitialCard::Joker),
+            'Q' => Some(InitialCard::Value(CardValue::Q)),
+            'K' => Some(InitialCard::Value(CardValue::K)),
+            'A' => Some(InitialCard::Value(CardValue::A)),
+            _ => None
         }
     }
 }
 
-fn winnings(bids: &mut [FixedUpBid]) -> u32 {
-    bids.sort_by(|a, b| compare_hands(&a.cards, &b.cards));
-    bids.iter()
+pub fn winnings(bids: &[FixedUpBid]) -> Result<Answer, SolveError> {
+    let mut ranked: Vec<&FixedUpBid> = bids.iter().collect();
+    ranked.sort_by(|a, b| compare_hands(&a.cards, &b.cards));
+    let values = ranked.iter()
         .enumerate()
-        .map(|(i, bid)| bid.value * (i as u32 + 1))
-        .sum()
+        .map(|(i, bid)| Answer::from(bid.value).checked_mul(i as Answer + 1));
+    solution::checked_sum(DAY, 2, "the total winnings", values)
 }
 
-fn compare_hands(a: &[FixedUpCard], b: &[FixedUpCard]) -> std::cmp::Ordering {
+pub fn compare_hands(a: &[FixedUpCard], b: &[FixedUpCard]) -> std::cmp::Ordering {
     let hand_a = hand_type(a) as u8;
     let hand_b = hand_type(b) as u8;
 
@@ -197,43 +194,27 @@ fn compare_hands(a: &[FixedUpCard], b: &[FixedUpCard]) -> std::cmp::Ordering {
     }
 }
 
-fn hand_type(cards: &[FixedUpCard]) -> HandType {
-    let mut occurrences = std::collections::HashMap::<u32, u8>::new();
-
-    for &card in cards {
-        let key: u32 = match card {
+pub fn hand_type(cards: &[FixedUpCard]) -> HandType {
+    let counter: Counter<u32> = cards.iter()
+        .map(|&card| match card {
             FixedUpCard::Regular { value } => value.value(),
             FixedUpCard::Joker { replaced_with } => replaced_with.value()
-        };
-
-        if let Some(n) = occurrences.get(&key) {
-            occurrences.insert(key, n + 1);
-        } else {
-            occurrences.insert(key, 1);
-        }
-    }
-
-    let values: Vec<u8> = occurrences.values().copied().collect();
+        })
+        .collect();
 
-    if values.contains(&5) {
-        HandType::FiveOfAKind
-    } else if values.contains(&4) {
-        HandType::FourOfAKind
-    } else if values.contains(&3) && values.contains(&2) {
-        HandType::FullHouse
-    } else if values.contains(&3) {
-        HandType::ThreeOfAKind
-    } else if values.contains(&2) && values.len() == 3 {
-        HandType::TwoPair
-    } else if values.contains(&2) {
-        HandType::OnePair
-    } else {
-        assert!(values.iter().all(|&v| v == 1), "{:?}", values);
-        HandType::HighCard
+    match counter.signature().as_slice() {
+        [5] => HandType::FiveOfAKind,
+        [4, 1] => HandType::FourOfAKind,
+        [3, 2] => HandType::FullHouse,
+        [3, 1, 1] => HandType::ThreeOfAKind,
+        [2, 2, 1] => HandType::TwoPair,
+        [2, 1, 1, 1] => HandType::OnePair,
+        [1, 1, 1, 1, 1] => HandType::HighCard,
+        signature => panic!("not a hand of 5 cards: {:?}", signature)
     }
 }
 
-fn replace_jokers(bids: &[Bid]) -> Vec<FixedUpBid> {
+pub fn replace_jokers(bids: &[Bid]) -> Vec<FixedUpBid> {
     bids.iter()
         .map(|bid| FixedUpBid {
             cards: replace_jokers_in_hand(&bid.cards),
@@ -242,61 +223,23 @@ fn replace_jokers(bids: &[Bid]) -> Vec<FixedUpBid> {
         .collect()
 }
 
-fn replace_jokers_in_hand(cards: &Vec<InitialCard>) -> Vec<FixedUpCard> {
-    let mut occurrences = std::collections::HashMap::<InitialCard, u8>::new();
-
-    for &card in cards {
-        if let Some(n) = occurrences.get(&card) {
-            occurrences.insert(card, n + 1);
-        } else {
-            occurrences.insert(card, 1);
-        }
-    }
-
-    if occurrences.get(&InitialCard::Joker).copied().unwrap_or(0) == 0 {
-        cards.iter()
-            .map(|card| match card {
-                InitialCard::Joker => unreachable!(),
-                InitialCard::Value(n) => FixedUpCard::Regular { value: *n }
-            })
-            .collect()
-    } else {
-        let jokers_count: u8 = *(occurrences.get(&InitialCard::Joker).unwrap());
-        assert!(jokers_count > 0);
-
-        if occurrences.len() == 1 {
-            // All aces
-            vec![FixedUpCard::Joker { replaced_with: CardValue::A }; 5]
-        } else {
-            let most_frequent_card: CardValue =
-                *(occurrences.iter()
-                  .max_by_key(|(&key, &value): &(&InitialCard, &u8)| match key {
-                      InitialCard::Joker => 0,
-                      InitialCard::Value(_) => value
-                  })
-                  .map(|(card, _)| match card {
-                      InitialCard::Joker => unreachable!(),
-                      InitialCard::Value(n) => n
-                  })
-                  .unwrap());
-            cards.iter()
-                .map(|&card| match card {
-                    InitialCard::Joker => FixedUpCard::Joker { replaced_with: most_frequent_card },
-                    InitialCard::Value(value) => FixedUpCard::Regular { value }
-                })
-                .collect()
-        }
-    }
-}
-
-#[test]
-fn test_part2() {
-    let input = vec![
-        "32T3K 765",
-        "T55J5 684",
-        "KK677 28",
-        "KTJJT 220",
-        "QQQJA 483"
-    ];
-    assert_eq!(part2_result(&input), 5905);
+pub fn replace_jokers_in_hand(cards: &[InitialCard]) -> Vec<FixedUpCard> {
+    let values: Counter<CardValue> = cards.iter()
+        .filter_map(|&card| match card {
+            InitialCard::Joker => None,
+            InitialCard::Value(value) => Some(value)
+        })
+        .collect();
+    // Jokers all become the most frequent other card, or aces when there is
+    // none.
+    let replaced_with = values.most_common()
+        .first()
+        .map_or(CardValue::A, |&(&value, _)| value);
+
+    cards.iter()
+        .map(|&card| match card {
+            InitialCard::Joker => FixedUpCard::Joker { replaced_with },
+            InitialCard::Value(value) => FixedUpCard::Regular { value }
+        })
+        .collect()
 }
@@ -0,0 +1,249 @@
+use crate::generate;
+use crate::solution::{Answer, Solution};
+use crate::{day4, day5, day6};
+use std::ops::Range;
+
+// Solves one part from the raw input, or returns None if the input doesn't
+// parse, which makes it unusable as a test case.
+pub type Solver = fn(&str) -> Option<Answer>;
+
+// A slow implementation that is obviously right, and a fast one that should
+// always agree with it.
+pub struct Pair {
+    pub name: &'static str,
+    pub day: u32,
+    pub reference: Solver,
+    pub fast: Solver
+}
+
+// The other parts have no reference on purpose: day 1, day 2, day 3, day 4
+// part 1, day 5 part 1 and both parts of day 7 are already the direct reading
+// of the puzzle, with nothing faster to check against it.
+pub const PAIRS: &[Pair] = &[
+    Pair {
+        name: "day 4 part 2, copy loop vs added counts",
+        day: 4,
+        reference: |input| Some(day4::part2_copy_loop(&day4::Day4.parse(input).ok()?)),
+        fast: |input| day4::part2_result(&day4::Day4.parse(input).ok()?).ok()
+    },
+    Pair {
+        name: "day 5 part 2, every seed vs split ranges",
+        day: 5,
+        reference: |input| day5::part2_brute_force(&day5::Day5.parse(input).ok()?).ok(),
+        fast: |input| day5::part2_result(&day5::Day5.parse(input).ok()?).ok()
+    },
+    Pair {
+        name: "day 6 part 1, scan vs quadratic",
+        day: 6,
+        reference: |input| Some(day6::Day6.parse(input).ok()?.races.into_iter()
+            .map(day6::win_possibilities_scan)
+            .product()),
+        fast: |input| Some(day6::Day6.parse(input).ok()?.races.into_iter()
+            .map(day6::win_possibilities)
+            .product())
+    },
+    Pair {
+        name: "day 6 part 2, scan vs quadratic",
+        day: 6,
+        reference: |input| Some(day6::win_possibilities_scan(day6::Day6.parse(input).ok()?.kerned_race)),
+        fast: |input| Some(day6::win_possibilities(day6::Day6.parse(input).ok()?.kerned_race))
+    }
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    Answer(Answer),
+    Panic(String)
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Answer(answer) => write!(f, "{}", answer),
+            Self::Panic(message) => write!(f, "panicked: {}", message)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Disagreement {
+    pub name: &'static str,
+    pub seed: u64,
+    pub size: usize,
+    // The smallest input found that still shows the disagreement.
+    pub input: String,
+    pub reference: Outcome,
+    pub fast: Outcome
+}
+
+impl std::fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: disagreement on seed {} size {}, shrunk to:\n{}\nreference: {}\nfast: {}",
+            self.name,
+            self.seed,
+            self.size,
+            self.input.trim_end(),
+            self.reference,
+            self.fast
+        )
+    }
+}
+
+fn run(solver: Solver, input: &str) -> Option<Outcome> {
+    match std::panic::catch_unwind(|| solver(input)) {
+        Ok(answer) => answer.map(Outcome::Answer),
+        Err(payload) => {
+            let message = payload.downcast_ref::<&str>().map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Some(Outcome::Panic(message))
+        }
+    }
+}
+
+// Both outcomes if the solvers disagree on a valid input.
+fn disagreement(pair: &Pair, input: &str) -> Option<(Outcome, Outcome)> {
+    let reference = run(pair.reference, input)?;
+    let fast = run(pair.fast, input)?;
+    (reference != fast).then_some((reference, fast))
+}
+
+// Runs both solvers on the generated inputs for every seed, and shrinks the
+// first input they disagree on.
+pub fn check(pair: &Pair, seeds: Range<u64>, size: usize) -> Result<(), Disagreement> {
+    let generator = generate::generator(pair.day).unwrap();
+
+    for seed in seeds {
+        let input = generator(&mut crate::rng::Rng::new(seed), size);
+
+        if disagreement(pair, &input).is_some() {
+            let input = shrink(pair, input);
+            let (reference, fast) = disagreement(pair, &input).unwrap();
+            return Err(Disagreement { name: pair.name, seed, size, input, reference, fast });
+        }
+    }
+
+    Ok(())
+}
+
+// Bounds the time spent shrinking when numbers can only go down one by one.
+const MAX_SHRINK_STEPS: usize = 1000;
+
+// Greedily applies the first simplification that keeps the solvers
+// disagreeing, until none does.
+pub fn shrink(pair: &Pair, mut input: String) -> String {
+    for _ in 0..MAX_SHRINK_STEPS {
+        match simplifications(&input).into_iter().find(|candidate| disagreement(pair, candidate).is_some()) {
+            Some(simpler) => input = simpler,
+            None => break
+        }
+    }
+
+    input
+}
+
+// Smaller variants of the input: without one of its lines, without one or
+// two of its space-separated tokens, or with a number made smaller.
+fn simplifications(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let rebuild = |i: usize, line: Option<String>| {
+        let mut lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+
+        match line {
+            Some(line) => lines[i] = line,
+            None => {
+                lines.remove(i);
+            }
+        }
+
+        lines.iter().map(|line| format!("{}\n", line)).collect::<String>()
+    };
+    let mut result = Vec::new();
+
+    for i in 0..lines.len() {
+        result.push(rebuild(i, None));
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        let tokens: Vec<&str> = line.split(' ').collect();
+
+        for j in 0..tokens.len() {
+            for count in [2, 1] {
+                if j + count <= tokens.len() {
+                    let mut rest = tokens.clone();
+                    rest.drain(j..j + count);
+                    result.push(rebuild(i, Some(rest.join(" "))));
+                }
+            }
+        }
+
+        for (j, token) in tokens.iter().enumerate() {
+            let Ok(n) = token.parse::<u64>() else {
+                continue;
+            };
+
+            for smaller in [0, 1, n / 2, n.saturating_sub(1)] {
+                if smaller < n {
+                    let mut tokens = tokens.clone();
+                    let smaller = smaller.to_string();
+                    tokens[j] = &smaller;
+                    result.push(rebuild(i, Some(tokens.join(" "))));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+#[test]
+fn test_pairs_agree() {
+    // Larger sizes make the references too slow in debug builds, see
+    // test_pairs_agree_on_larger_inputs.
+    for pair in PAIRS {
+        for size in [1, 2, 3] {
+            if let Err(disagreement) = check(pair, 0..50, size) {
+                panic!("{}", disagreement);
+            }
+        }
+    }
+}
+
+// Day 4 only copies many cards, and day 5 only splits seed ranges along many
+// map ranges, once inputs get larger, but so do the references' run times:
+// run with --ignored, ideally with --release.
+#[test]
+#[ignore]
+fn test_pairs_agree_on_larger_inputs() {
+    for pair in PAIRS {
+        for size in [10, 20, 50] {
+            if let Err(disagreement) = check(pair, 0..50, size) {
+                panic!("{}", disagreement);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_shrink() {
+    // Disagrees as soon as there is a number above 15.
+    let pair = Pair {
+        name: "sum",
+        day: 2,
+        reference: |input| Some(input.split(|c: char| !c.is_ascii_digit())
+            .filter_map(|n| n.parse::<Answer>().ok())
+            .sum()),
+        fast: |input| Some(input.split(|c: char| !c.is_ascii_digit())
+            .filter_map(|n| n.parse::<Answer>().ok())
+            .filter(|&n| n <= 15)
+            .sum())
+    };
+    assert_eq!(
+        shrink(&pair, "Game 1: 3 blue, 4 red\nGame 2: 60 green; 7 blue, 80 red\n".to_owned()),
+        "16\n"
+    );
+    assert!(check(&pair, 0..10, 3).is_err());
+    assert!(check(&PAIRS[0], 0..10, 3).is_ok());
+}
@@ -0,0 +1,182 @@
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub day: u32,
+    // Both 1-based, the column counting characters rather than bytes.
+    pub line: usize,
+    pub column: usize,
+    pub width: usize,
+    pub text: String,
+    pub message: String
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        writeln!(
+            f,
+            "day {}, line {}, column {}: {}",
+            self.day,
+            self.line,
+            self.column,
+            self.message
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", number, self.text)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width.max(1))
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// An error somewhere within a line, before the caller attaches the day and
+// line number. The offending token has to be a slice of that line, which lets
+// its column be recovered from its address without threading offsets through
+// every parser.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineError {
+    address: usize,
+    len: usize,
+    message: String
+}
+
+impl LineError {
+    pub fn new(token: &str, message: impl Into<String>) -> Self {
+        Self {
+            address: token.as_ptr() as usize,
+            len: token.len(),
+            message: message.into()
+        }
+    }
+
+    // Blames the whole line, e.g. when something is missing from it.
+    pub fn whole(line: &str, message: impl Into<String>) -> Self {
+        Self::new(line, message)
+    }
+
+    // `index` is the 0-based index of the line in the input.
+    pub fn locate(self, day: u32, index: usize, line: &str) -> ParseError {
+        let line_start = line.as_ptr() as usize;
+        let (start, end) = if self.address >= line_start
+            && self.address + self.len <= line_start + line.len()
+        {
+            let start = self.address - line_start;
+            (start, start + self.len)
+        } else {
+            (line.len(), line.len())
+        };
+        let column = line.get(..start)
+            .map(|before| before.chars().count())
+            .unwrap_or(start) + 1;
+        let width = line.get(start..end)
+            .map(|token| token.chars().count())
+            .unwrap_or(end - start);
+        ParseError {
+            day,
+            line: index + 1,
+            column,
+            width,
+            text: line.to_owned(),
+            message: self.message
+        }
+    }
+}
+
+// For errors about the input as a whole, reported just past its last line.
+pub fn missing(day: u32, lines: usize, message: impl Into<String>) -> ParseError {
+    ParseError {
+        day,
+        line: lines + 1,
+        column: 1,
+        width: 1,
+        text: String::new(),
+        message: message.into()
+    }
+}
+
+// Why a part has no answer for input that parsed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SolveError {
+    // Input that only one part can't make sense of, like a day 1 line whose
+    // digits are all spelled out for part 1.
+    Parse(ParseError),
+    // An answer, or a value it is built from, too large for an Answer.
+    Overflow { day: u32, part: u32, what: &'static str },
+    // Input with nothing to find an answer in, e.g. unpaired seeds for day 5.
+    Unsolvable { day: u32, part: u32, reason: &'static str }
+}
+
+impl std::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Parse(error) => write!(f, "{}", error),
+            Self::Overflow { day, part, what } => write!(f, "day {}, part {}: {} overflows", day, part, what),
+            Self::Unsolvable { day, part, reason } => write!(f, "day {}, part {}: {}", day, part, reason)
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+impl From<ParseError> for SolveError {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+#[test]
+fn test_locate() {
+    let line = "Game 3: 8 purple, 6 blue";
+    let token = &line[10..16];
+    let error = LineError::new(token, "unknown color \"purple\"")
+        .locate(2, 2, line);
+    assert_eq!(
+        error,
+        ParseError {
+            day: 2,
+            line: 3,
+            column: 11,
+            width: 6,
+            text: line.to_owned(),
+            message: "unknown color \"purple\"".to_owned()
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "day 2, line 3, column 11: unknown color \"purple\"\n  \
+         |\n\
+         3 | Game 3: 8 purple, 6 blue\n  \
+         |           ^^^^^^"
+    );
+}
+
+#[test]
+fn test_locate_counts_characters() {
+    let line = "é1x";
+    let error = LineError::new(&line[3..4], "unexpected").locate(1, 0, line);
+    assert_eq!((error.column, error.width), (3, 1));
+}
+
+#[test]
+fn test_locate_foreign_token() {
+    let line = "seeds 1 2";
+    let error = LineError::new("seeds:", "expected \"seeds:\"").locate(5, 0, line);
+    assert_eq!((error.column, error.width), (10, 0));
+    assert!(error.to_string().ends_with("          ^"));
+}
+
+#[test]
+fn test_solve_error() {
+    let error = SolveError::from(missing(1, 2, "expected a digit"));
+    assert!(error.to_string().starts_with("day 1, line 3, column 1: expected a digit"));
+    let error = SolveError::Overflow { day: 4, part: 2, what: "the number of copies" };
+    assert_eq!(error.to_string(), "day 4, part 2: the number of copies overflows");
+    let error = SolveError::Unsolvable { day: 5, part: 2, reason: "expected seeds in pairs of start and length" };
+    assert_eq!(error.to_string(), "day 5, part 2: expected seeds in pairs of start and length");
+}
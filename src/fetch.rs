@@ -0,0 +1,126 @@
+use crate::http;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2023";
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+pub const DEFAULT_SESSION_FILE: &str = ".aoc-session";
+const USER_AGENT: &str = "github.com/bastienleonard/adventofcode2023";
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf)
+}
+
+// The session cookie of a logged in browser. The environment variable takes
+// precedence over the file.
+pub fn session(file: &Path) -> Result<String, String> {
+    let session = match std::env::var(SESSION_VARIABLE) {
+        Ok(session) => session,
+        Err(_) => std::fs::read_to_string(file).map_err(|error| {
+            format!(
+                "No session token: set {} or write it to {} ({})",
+                SESSION_VARIABLE,
+                file.display(),
+                error
+            )
+        })?
+    };
+    let session = session.trim();
+
+    if session.is_empty() {
+        return Err("The session token is empty".to_owned());
+    }
+
+    Ok(session.to_owned())
+}
+
+pub fn headers(session: &str) -> [(&'static str, String); 2] {
+    [("Cookie", format!("session={}", session)), ("User-Agent", USER_AGENT.to_owned())]
+}
+
+// Inputs never change for a given account, so an existing file is never
+// downloaded again. Empty files, such as the ones new-day creates, don't count.
+pub fn fetch(
+    base_url: &str,
+    day: u32,
+    session: &str,
+    directory: &Path
+) -> Result<Fetched, String> {
+    let path = directory.join(format!("{}.txt", day));
+
+    if std::fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let url = format!("{}/day/{}/input", base_url.trim_end_matches('/'), day);
+    let headers = headers(session);
+    let headers: Vec<(&str, &str)> = headers.iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect();
+    let response = http::send("GET", &url, &headers, None)?;
+
+    if response.status != 200 {
+        return Err(format!(
+            "GET {} failed with status {}: {}",
+            url,
+            response.status,
+            response.body.trim()
+        ));
+    }
+
+    if response.body.is_empty() {
+        return Err(format!("GET {} returned an empty input", url));
+    }
+
+    // Write through a temporary file so that an interrupted download doesn't
+    // leave a truncated input behind to be cached forever.
+    let write_error = |error: std::io::Error| format!("cannot write {}: {}", path.display(), error);
+    let partial = directory.join(format!("{}.txt.partial", day));
+    std::fs::create_dir_all(directory).map_err(write_error)?;
+    std::fs::write(&partial, &response.body).map_err(write_error)?;
+    std::fs::rename(&partial, &path).map_err(write_error)?;
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+fn temporary_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir()
+        .join(format!("adventofcode2023-{}-{}", name, std::process::id()));
+    std::fs::remove_dir_all(&directory).ok();
+    directory
+}
+
+#[test]
+fn test_fetch_downloads_then_caches() {
+    let directory = temporary_directory("fetch");
+    let (url, server) = http::serve(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 8\r\n\r\n1abc2\n3\n".to_owned()
+    ]);
+    let path = directory.join("3.txt");
+
+    assert_eq!(fetch(&url, 3, "abc", &directory), Ok(Fetched::Downloaded(path.clone())));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "1abc2\n3\n");
+    // The server only answers once, so this would fail if it were contacted.
+    assert_eq!(fetch(&url, 3, "abc", &directory), Ok(Fetched::Cached(path)));
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /day/3/input HTTP/1.1\r\n"));
+    assert!(requests[0].contains("\r\nCookie: session=abc\r\n"));
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_fetch_errors() {
+    let directory = temporary_directory("fetch-errors");
+    let (url, server) = http::serve(vec![
+        "HTTP/1.1 400 Bad Request\r\nContent-Length: 11\r\n\r\nlog in pls\n".to_owned(),
+        "HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".to_owned()
+    ]);
+
+    assert!(fetch(&url, 4, "expired", &directory).unwrap_err().contains("status 400"));
+    assert!(fetch(&url, 4, "abc", &directory).unwrap_err().contains("empty"));
+    assert!(!directory.join("4.txt").exists());
+    server.join().unwrap();
+    std::fs::remove_dir_all(&directory).ok();
+}
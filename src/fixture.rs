@@ -0,0 +1,92 @@
+use crate::solution::Answer;
+use std::path::Path;
+
+pub const DEFAULT_DIRECTORY: &str = "examples";
+
+pub const ANSWERS_HEADER: &str = "# part answer";
+
+// A puzzle example: <directory>/<day>/<name>.txt holds the input, and
+// <name>.answers the expected answer of each part that applies to it.
+#[derive(Debug, PartialEq)]
+pub struct Fixture {
+    pub day: u32,
+    pub name: String,
+    pub input: String,
+    pub answers: Vec<(u32, Answer)>
+}
+
+pub fn load_all(directory: &Path) -> Result<Vec<Fixture>, String> {
+    let mut result = Vec::new();
+
+    for entry in read_dir(directory)? {
+        let day = match entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) {
+            Some(day) if entry.path().is_dir() => day,
+            _ => continue
+        };
+
+        for entry in read_dir(&entry.path())? {
+            let path = entry.path();
+
+            if path.extension().is_none_or(|extension| extension != "txt") {
+                continue;
+            }
+
+            let answers_path = path.with_extension("answers");
+            result.push(Fixture {
+                day,
+                name: path.file_stem().unwrap().to_string_lossy().into_owned(),
+                input: read(&path)?,
+                answers: parse_answers(&read(&answers_path)?)
+                    .map_err(|error| format!("{}: {}", answers_path.display(), error))?
+            });
+        }
+    }
+
+    result.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    Ok(result)
+}
+
+fn read_dir(directory: &Path) -> Result<Vec<std::fs::DirEntry>, String> {
+    std::fs::read_dir(directory)
+        .and_then(|entries| entries.collect())
+        .map_err(|error| format!("cannot read {}: {}", directory.display(), error))
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path)
+        .map_err(|error| format!("cannot read {}: {}", path.display(), error))
+}
+
+pub fn parse_answers(text: &str) -> Result<Vec<(u32, Answer)>, String> {
+    let mut answers = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let invalid = || format!("invalid entry on line {}: {:?}", i + 1, line);
+        let (part, answer) = line.split_once(' ').ok_or_else(invalid)?;
+        let part = part.parse::<u32>().map_err(|_| invalid())?;
+        let answer = answer.trim().parse::<Answer>().map_err(|_| invalid())?;
+
+        if !(1..=2).contains(&part) {
+            return Err(invalid());
+        }
+
+        answers.push((part, answer));
+    }
+
+    Ok(answers)
+}
+
+#[test]
+fn test_parse_answers() {
+    assert_eq!(parse_answers("# part answer\n1 35\n\n2 46\n"), Ok(vec![(1, 35), (2, 46)]));
+    assert_eq!(parse_answers("# part answer\n"), Ok(vec![]));
+    assert!(parse_answers("1").is_err());
+    assert!(parse_answers("3 35").is_err());
+    assert!(parse_answers("1 -35").is_err());
+}
@@ -0,0 +1,219 @@
+use crate::generate;
+use crate::registry;
+use crate::rng::Rng;
+use crate::runner;
+use crate::solution::AnySolution;
+
+// Pieces that the parsers give a meaning to, so that mutations get past their
+// first checks, along with numbers that overflow and bytes that aren't ASCII
+// or even UTF-8.
+const TOKENS: &[&[u8]] = &[
+    b" ",
+    b"  ",
+    b"\n",
+    b"\n\n",
+    b"\r\n",
+    b"\t",
+    b":",
+    b": ",
+    b";",
+    b",",
+    b"|",
+    b"-",
+    b"*",
+    b".",
+    b"0",
+    b"9",
+    b"4294967295",
+    b"4294967296",
+    b"18446744073709551616",
+    b"99999999999999999999999999999999999999999",
+    b"Game ",
+    b"Card ",
+    b"red",
+    b"seeds:",
+    b"-to-",
+    b" map:",
+    b"Time:",
+    b"Distance:",
+    b"J",
+    "\u{e9}".as_bytes(),
+    "\u{d7}".as_bytes(),
+    "\u{1f600}".as_bytes(),
+    b"\xff",
+    b"\xc3"
+];
+
+#[derive(Debug)]
+pub struct Crash {
+    pub day: u32,
+    pub seed: u64,
+    // The smallest input found that still makes the target panic.
+    pub input: Vec<u8>,
+    pub message: String
+}
+
+impl std::fmt::Display for Crash {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "day {}: panicked on seed {} with {:?}, shrunk to:\n{:?}",
+            self.day,
+            self.seed,
+            self.message,
+            String::from_utf8_lossy(&self.input)
+        )
+    }
+}
+
+// The fuzz target of a day: arbitrary bytes go through its parser, which has
+// to either accept them or return an error that can be displayed, and then
+// through both parts when they parse, which have to either answer or return
+// such an error. Bytes that aren't UTF-8 are replaced, as inputs are read as
+// text.
+pub fn target(solution: &dyn AnySolution, input: &[u8]) -> Result<(), String> {
+    let text = String::from_utf8_lossy(input);
+    let target = std::panic::AssertUnwindSafe(|| {
+        let parsed = match solution.parse_any(&text) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                error.to_string();
+                return;
+            }
+        };
+
+        for part in [1, 2] {
+            if let Err(error) = solution.solve_any(parsed.as_ref(), part) {
+                error.to_string();
+            }
+        }
+    });
+    std::panic::catch_unwind(target).map_err(|payload| runner::panic_message(payload.as_ref()))
+}
+
+// Mutates generated inputs of the day, starting over from a fresh one every
+// so often, and shrinks the first input that makes the target panic.
+pub fn fuzz(day: u32, seed: u64, iterations: usize) -> Result<(), Crash> {
+    let solution = registry::find(day).unwrap();
+    let mut rng = Rng::new(seed);
+    let mut input = Vec::new();
+
+    for i in 0..iterations {
+        if i % 100 == 0 {
+            let size = rng.range(1..=5) as usize;
+            input = generate::generate(day, rng.next_u64(), size).unwrap().into_bytes();
+        }
+
+        input = mutate(&mut rng, input);
+
+        if let Err(message) = target(solution, &input) {
+            let input = shrink(solution, input);
+            return Err(Crash { day, seed, input, message });
+        }
+    }
+
+    Ok(())
+}
+
+// Applies one to four random edits, each at a random position.
+pub fn mutate(rng: &mut Rng, mut input: Vec<u8>) -> Vec<u8> {
+    for _ in 0..rng.range(1..=4) {
+        let i = rng.below(input.len() as u64 + 1) as usize;
+
+        match rng.below(5) {
+            0 => {
+                input.splice(i..i, rng.choose(TOKENS).iter().copied());
+            }
+            1 if i < input.len() => {
+                let end = (i + rng.range(1..=8) as usize).min(input.len());
+                input.drain(i..end);
+            }
+            2 if i < input.len() => {
+                input[i] = rng.below(256) as u8;
+            }
+            3 if i < input.len() => {
+                let end = (i + rng.range(1..=16) as usize).min(input.len());
+                let copy = input[i..end].to_vec();
+                let j = rng.below(input.len() as u64 + 1) as usize;
+                input.splice(j..j, copy);
+            }
+            _ => {
+                input.insert(i, b"0123456789"[rng.below(10) as usize]);
+            }
+        }
+    }
+
+    input
+}
+
+// Greedily removes lines, then single bytes, as long as the target still
+// panics.
+pub fn shrink(solution: &dyn AnySolution, mut input: Vec<u8>) -> Vec<u8> {
+    loop {
+        let lines: Vec<&[u8]> = input.split(|&byte| byte == b'\n').collect();
+        let without_line = (0..lines.len()).map(|i| {
+            let mut rest = lines.clone();
+            rest.remove(i);
+            rest.join(&b'\n')
+        });
+        let without_byte = (0..input.len()).map(|i| {
+            let mut rest = input.clone();
+            rest.remove(i);
+            rest
+        });
+        let simpler = without_line.chain(without_byte)
+            .find(|candidate| target(solution, candidate).is_err());
+
+        match simpler {
+            Some(simpler) => input = simpler,
+            None => return input
+        }
+    }
+}
+
+#[test]
+fn test_days_dont_panic() {
+    for solution in registry::SOLUTIONS.iter().filter(|solution| !registry::UNSOLVED.contains(&solution.day())) {
+        if let Err(crash) = fuzz(solution.day(), 0, 2000) {
+            panic!("{}", crash);
+        }
+    }
+}
+
+#[test]
+fn test_shrink() {
+    use crate::error::{ParseError, SolveError};
+    use crate::solution::{Answer, Solution};
+
+    // Panics on any input with two nines in a row, or in part 2 with two
+    // sevens.
+    struct Picky;
+
+    impl Solution for Picky {
+        type Parsed = String;
+
+        fn day(&self) -> u32 {
+            1
+        }
+
+        fn parse(&self, input: &str) -> Result<String, ParseError> {
+            assert!(!input.contains("99"));
+            Ok(input.to_owned())
+        }
+
+        fn part1(&self, _: &String) -> Result<Answer, SolveError> {
+            Ok(0)
+        }
+
+        fn part2(&self, input: &String) -> Result<Answer, SolveError> {
+            assert!(!input.contains("77"));
+            Ok(0)
+        }
+    }
+
+    assert!(target(&Picky, b"1 2\n3 9").is_ok());
+    assert!(target(&Picky, b"1 2\n3 99").is_err());
+    assert_eq!(shrink(&Picky, b"Game 1: 3 blue\nGame 2: 199 red; 4 green\n".to_vec()), b"99");
+    assert!(target(&Picky, b"1 2\n3 77").is_err());
+    assert_eq!(shrink(&Picky, b"Game 1: 3 blue\nGame 2: 177 red; 4 green\n".to_vec()), b"77");
+}
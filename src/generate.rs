@@ -0,0 +1,310 @@
+use crate::rng::Rng;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+// Every generator returns an input that its day accepts. `size` scales the
+// input, mostly as a number of lines, but it's only a hint where real inputs
+// have a fixed shape.
+pub fn generator(day: u32) -> Option<Generator> {
+    match day {
+        1 => Some(day1),
+        2 => Some(day2),
+        3 => Some(day3),
+        4 => Some(day4),
+        5 => Some(day5),
+        6 => Some(day6),
+        7 => Some(day7),
+        _ => None
+    }
+}
+
+pub fn generate(day: u32, seed: u64, size: usize) -> Option<String> {
+    generator(day).map(|generator| generator(&mut Rng::new(seed), size))
+}
+
+const DIGIT_WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+// `size` calibration lines, each with at least one actual digit so that both
+// parts accept them.
+pub fn day1(rng: &mut Rng, size: usize) -> String {
+    let mut result = String::new();
+
+    for _ in 0..size {
+        let mut pieces: Vec<String> = Vec::new();
+
+        for _ in 0..rng.range(1..=6) {
+            pieces.push(match rng.below(3) {
+                0 => rng.range(1..=9).to_string(),
+                1 => rng.choose(&DIGIT_WORDS).to_string(),
+                _ => letters(rng, 1..=4)
+            });
+        }
+
+        if !pieces.iter().any(|piece| piece.starts_with(|c: char| c.is_ascii_digit())) {
+            let i = rng.below(pieces.len() as u64 + 1) as usize;
+            pieces.insert(i, rng.range(1..=9).to_string());
+        }
+
+        writeln!(result, "{}", pieces.concat()).unwrap();
+    }
+
+    result
+}
+
+fn letters(rng: &mut Rng, count: std::ops::RangeInclusive<u64>) -> String {
+    (0..rng.range(count))
+        .map(|_| (b'a' + rng.below(26) as u8) as char)
+        .collect()
+}
+
+// `size` games of one to six sets.
+pub fn day2(rng: &mut Rng, size: usize) -> String {
+    let mut result = String::new();
+
+    for id in 1..=size {
+        let sets: Vec<String> = (0..rng.range(1..=6))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                rng.shuffle(&mut colors);
+                colors[..rng.range(1..=3) as usize].iter()
+                    .map(|color| format!("{} {}", rng.range(1..=20), color))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            })
+            .collect();
+        writeln!(result, "Game {}: {}", id, sets.join("; ")).unwrap();
+    }
+
+    result
+}
+
+const SYMBOLS: &[u8] = b"**#+$/@=%-&";
+
+// A `size` by `size` schematic, with numbers always separated by at least one
+// other character on their row.
+pub fn day3(rng: &mut Rng, size: usize) -> String {
+    let mut result = String::new();
+
+    for _ in 0..size {
+        let mut row = String::new();
+
+        while row.len() < size {
+            let left = size - row.len();
+
+            if rng.chance(20) {
+                let digits = rng.range(1..=3.min(left as u64)) as u32;
+                write!(row, "{}", rng.range(10u64.pow(digits - 1)..=10u64.pow(digits) - 1)).unwrap();
+
+                if row.len() < size {
+                    row.push('.');
+                }
+            } else if rng.chance(10) {
+                row.push(*rng.choose(SYMBOLS) as char);
+            } else {
+                row.push('.');
+            }
+        }
+
+        writeln!(result, "{}", row).unwrap();
+    }
+
+    result
+}
+
+// `size` scratchcards with 10 winning numbers and 25 numbers of one's own,
+// like real ones. Cards never win copies past the last card, and mostly win
+// few, so that part 2 doesn't explode too fast.
+pub fn day4(rng: &mut Rng, size: usize) -> String {
+    let mut result = String::new();
+
+    for id in 1..=size {
+        let mut numbers: Vec<u32> = (1..=99).collect();
+        rng.shuffle(&mut numbers);
+        let left = (size - id) as u64;
+        let matches = match rng.below(100) {
+            0..=59 => 0,
+            60..=84 => rng.range(1..=2),
+            _ => rng.range(3..=10)
+        }.min(left) as usize;
+        let winning = &numbers[..10];
+        let mut own: Vec<u32> = winning[..matches].iter()
+            .chain(&numbers[10..35 - matches])
+            .copied()
+            .collect();
+        rng.shuffle(&mut own);
+        let format = |numbers: &[u32]| numbers.iter()
+            .map(|n| format!("{:>2}", n))
+            .collect::<Vec<String>>()
+            .join(" ");
+        writeln!(result, "Card {:>3}: {} | {}", id, format(winning), format(&own)).unwrap();
+    }
+
+    result
+}
+
+const RESOURCES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location"
+];
+
+// `size` seed ranges, and maps of about `size` ranges each. Like in real
+// almanacs, each map moves blocks around: sources don't overlap, and neither
+// do destinations. Values stay below 1000 * `size` so that brute forcing
+// part 2 stays cheap.
+pub fn day5(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1) as u64;
+    let limit = 1000 * size;
+    let seeds: Vec<String> = (0..size)
+        .map(|_| format!("{} {}", rng.below(limit - 100), rng.range(1..=100)))
+        .collect();
+    let mut result = format!("seeds: {}\n", seeds.join(" "));
+
+    for resources in RESOURCES.windows(2) {
+        let count = rng.range(1..=size + 2);
+        let lengths: Vec<u64> = (0..count)
+            .map(|_| rng.range(1..=limit / count / 2))
+            .collect();
+        let mut order: Vec<usize> = (0..lengths.len()).collect();
+        rng.shuffle(&mut order);
+        let mut destinations = vec![0; lengths.len()];
+        let mut start = rng.below(limit / 4);
+
+        for &i in &order {
+            destinations[i] = start;
+            start += lengths[i] + rng.below(10);
+        }
+
+        let mut ranges = Vec::new();
+        let mut start = rng.below(limit / 4);
+
+        for (i, &length) in lengths.iter().enumerate() {
+            ranges.push(format!("{} {} {}", destinations[i], start, length));
+            start += length + rng.below(10);
+        }
+
+        rng.shuffle(&mut ranges);
+        write!(result, "\n{}-to-{} map:\n{}\n", resources[0], resources[1], ranges.join("\n")).unwrap();
+    }
+
+    result
+}
+
+// Races that can all be won. There are at most 4 of them whatever `size`, as
+// part 2 reads them as one race, whose duration has to fit in 8 digits for
+// the linear scan to stay practical.
+pub fn day6(rng: &mut Rng, size: usize) -> String {
+    let races: Vec<(u64, u64)> = (0..size.clamp(1, 4))
+        .map(|_| {
+            let duration = rng.range(7..=99);
+            let record = (duration / 2) * (duration - duration / 2);
+            (duration, rng.below(record))
+        })
+        .collect();
+    let width = races.iter()
+        .map(|(duration, distance)| duration.to_string().len().max(distance.to_string().len()))
+        .max()
+        .unwrap();
+    let mut result = String::new();
+
+    for (label, values) in [
+        ("Time:", races.iter().map(|race| race.0).collect::<Vec<u64>>()),
+        ("Distance:", races.iter().map(|race| race.1).collect())
+    ] {
+        write!(result, "{:<9}", label).unwrap();
+
+        for value in values {
+            write!(result, " {:>width$}", value, width = width + 2).unwrap();
+        }
+
+        result.push('\n');
+    }
+
+    result
+}
+
+const CARDS: &[u8] = b"23456789TJQKA";
+
+// `size` distinct hands, so that ranks are well defined. Hands are drawn from
+// a few labels at a time to get every hand type.
+pub fn day7(rng: &mut Rng, size: usize) -> String {
+    let size = size.min(CARDS.len().pow(5));
+    let mut hands = HashSet::new();
+    let mut result = String::new();
+
+    while hands.len() < size {
+        let mut labels = CARDS.to_vec();
+        rng.shuffle(&mut labels);
+        let labels = &labels[..rng.range(1..=5) as usize];
+        let hand: String = (0..5).map(|_| *rng.choose(labels) as char).collect();
+
+        if hands.insert(hand.clone()) {
+            writeln!(result, "{} {}", hand, rng.range(1..=1000)).unwrap();
+        }
+    }
+
+    result
+}
+
+#[test]
+fn test_generated_inputs_are_accepted() {
+    for solution in crate::registry::SOLUTIONS {
+        let day = solution.day();
+
+        if crate::registry::UNSOLVED.contains(&day) {
+            continue;
+        }
+
+        for seed in 0..20 {
+            for size in [1, 2, 3, 20] {
+                let input = generate(day, seed, size).unwrap();
+                let context = format!("day {}, seed {} size {}:\n{}", day, seed, size, input);
+                assert!(solution.parse_any(&input).is_ok(), "{}", context);
+
+                // Solving is slow in debug builds for the larger inputs of
+                // some days, so only small ones are solved.
+                if size <= 3 {
+                    for part in [1, 2] {
+                        assert!(solution.solve(&input, part).is_ok(), "part {}, {}", part, context);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_generate_is_deterministic() {
+    for day in 1..=7 {
+        assert_eq!(generate(day, 3, 10), generate(day, 3, 10));
+        assert_ne!(generate(day, 3, 10), generate(day, 4, 10));
+    }
+
+    assert_eq!(generate(8, 3, 10), None);
+}
+
+#[test]
+fn test_day5_ranges_dont_overlap() {
+    use crate::solution::Solution;
+
+    for seed in 0..50 {
+        let input = day5(&mut Rng::new(seed), 5);
+        let almanac = crate::day5::Day5.parse(&input).unwrap();
+
+        for map in &almanac.maps {
+            for (i, a) in map.ranges.iter().enumerate() {
+                for b in &map.ranges[i + 1..] {
+                    assert!(a.destination_start + a.length <= b.destination_start
+                            || b.destination_start + b.length <= a.destination_start);
+                }
+            }
+        }
+    }
+}
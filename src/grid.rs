@@ -0,0 +1,264 @@
+use crate::error::{LineError, ParseError};
+use std::ops::{Index, IndexMut};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize
+}
+
+impl Point {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    // None when the move would go past the top or the left of any grid.
+    pub fn offset(self, (dx, dy): (isize, isize)) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?
+        })
+    }
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+// Moves as (dx, dy), y going down.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+pub const NEIGHBORS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1)
+];
+
+// Cells stored row after row, so that any cell is one index away.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "cells don't fill a {}x{} grid", width, height);
+        Self { width, height, cells }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    // Every line is a row, and `cell` reads each character of it. Rows must
+    // all have the same length.
+    pub fn parse(
+        input: &str,
+        day: u32,
+        mut cell: impl FnMut(char) -> Result<T, String>
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let locate = |error: LineError| error.locate(day, i, line);
+            let before = cells.len();
+
+            for (j, c) in line.char_indices() {
+                let value = cell(c)
+                    .map_err(|message| locate(LineError::new(&line[j..(j + c.len_utf8())], message)))?;
+                cells.push(value);
+            }
+
+            let row_width = cells.len() - before;
+
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(locate(LineError::whole(line, format!("expected {} cells", width))));
+                }
+                Some(_) => {}
+            }
+
+            height += 1;
+        }
+
+        Ok(Self::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point).then(|| &self.cells[point.y * self.width + point.x])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if self.contains(point) {
+            Some(&mut self.cells[point.y * self.width + point.x])
+        } else {
+            None
+        }
+    }
+
+    // The points reached by `moves` from `point` that are within the grid.
+    pub fn moves<'a>(
+        &self,
+        point: Point,
+        moves: &'a [(isize, isize)]
+    ) -> impl Iterator<Item = Point> + 'a {
+        let (width, height) = (self.width, self.height);
+        moves.iter()
+            .filter_map(move |&delta| point.offset(delta))
+            .filter(move |point| point.x < width && point.y < height)
+    }
+
+    pub fn neighbors(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.moves(point, &NEIGHBORS)
+    }
+
+    pub fn orthogonal_neighbors(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.moves(point, &ORTHOGONAL)
+    }
+
+    // Every point from `start` on, repeating the same move until leaving the
+    // grid.
+    pub fn ray(&self, start: Point, delta: (isize, isize)) -> impl Iterator<Item = Point> + '_ {
+        std::iter::successors(Some(start), move |point| point.offset(delta))
+            .take_while(|&point| self.contains(point))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.ray(Point::new(x, 0), (0, 1)).map(|point| &self[point])
+    }
+
+    // Down and to the right from `start`.
+    pub fn diagonal(&self, start: Point) -> impl Iterator<Item = &T> {
+        self.ray(start, (1, 1)).map(|point| &self[point])
+    }
+
+    // Down and to the left from `start`.
+    pub fn anti_diagonal(&self, start: Point) -> impl Iterator<Item = &T> {
+        self.ray(start, (-1, 1)).map(|point| &self[point])
+    }
+
+    // Row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{} is outside of a {}x{} grid", point, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{} is outside of a {}x{} grid", point, width, height))
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn sample() -> Grid<char> {
+    Grid::parse("abc\ndef", 0, Ok).unwrap()
+}
+
+#[test]
+fn test_parse() {
+    let grid = sample();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[Point::new(2, 1)], 'f');
+    assert_eq!(grid.get(Point::new(3, 0)), None);
+    assert_eq!(grid.to_string(), "abc\ndef\n");
+
+    let error = Grid::parse("abc\nde", 3, Ok).err().unwrap();
+    assert_eq!((error.day, error.line, error.column, error.width), (3, 2, 1, 2));
+
+    let digits = |c: char| c.to_digit(10).ok_or_else(|| format!("unexpected {:?}", c));
+    let error = Grid::parse("12\n3x", 3, digits).err().unwrap();
+    assert_eq!((error.line, error.column, error.message.as_str()), (2, 2, "unexpected 'x'"));
+    assert_eq!(Grid::parse("12\n34", 3, digits).unwrap().row(1), &[3, 4]);
+    assert_eq!(Grid::parse("", 3, digits).unwrap(), Grid::new(0, 0, Vec::new()));
+}
+
+#[test]
+fn test_neighbors() {
+    let grid = sample();
+    let corner: Vec<Point> = grid.neighbors(Point::new(0, 0)).collect();
+    assert_eq!(corner, [Point::new(1, 0), Point::new(0, 1), Point::new(1, 1)]);
+    assert_eq!(grid.neighbors(Point::new(1, 1)).count(), 5);
+    assert_eq!(grid.orthogonal_neighbors(Point::new(2, 1)).count(), 2);
+}
+
+#[test]
+fn test_iterators() {
+    let mut grid = sample();
+    assert_eq!(grid.column(1).collect::<String>(), "be");
+    assert_eq!(grid.diagonal(Point::new(0, 0)).collect::<String>(), "ae");
+    assert_eq!(grid.anti_diagonal(Point::new(2, 0)).collect::<String>(), "ce");
+    assert_eq!(grid.ray(Point::new(2, 1), (-1, 0)).count(), 3);
+    assert_eq!(grid.iter().nth(4), Some((Point::new(1, 1), &'e')));
+
+    grid[Point::new(1, 1)] = 'x';
+    assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDXF\n");
+    assert_eq!(Grid::filled(2, 1, 0).to_string(), "00\n");
+}
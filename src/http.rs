@@ -0,0 +1,227 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String
+}
+
+// Plain http:// URLs are handled here, which keeps local stand-in servers
+// dependency-free. TLS is left to curl.
+pub fn send(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>
+) -> Result<Response, String> {
+    if let Some(rest) = url.strip_prefix("http://") {
+        send_plain(method, rest, headers, body)
+    } else if url.starts_with("https://") {
+        send_with_curl(method, url, headers, body)
+    } else {
+        Err(format!("Unsupported URL {:?}", url))
+    }
+}
+
+fn send_plain(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>
+) -> Result<Response, String> {
+    let (host, path) = match url.find('/') {
+        Some(i) => (&url[..i], &url[i..]),
+        None => (url, "/")
+    };
+    let address = if host.contains(':') { host.to_owned() } else { format!("{}:80", host) };
+    let io_error = |error: std::io::Error| format!("{}: {}", host, error);
+    let mut stream = TcpStream::connect(&address).map_err(io_error)?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(io_error)?;
+
+    let mut request = format!("{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n", method, path, host);
+
+    for (name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+
+    if let Some(body) = body {
+        request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+
+    request.push_str("\r\n");
+    request.push_str(body.unwrap_or(""));
+    stream.write_all(request.as_bytes()).map_err(io_error)?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).map_err(io_error)?;
+    parse_response(&response)
+}
+
+fn parse_response(response: &[u8]) -> Result<Response, String> {
+    let invalid = || "Invalid HTTP response".to_owned();
+    let header_end = response.windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(invalid)?;
+    let head = std::str::from_utf8(&response[..header_end]).map_err(|_| invalid())?;
+    let mut lines = head.split("\r\n");
+    let status = lines.next()
+        .and_then(|line| line.split(' ').nth(1))
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(invalid)?;
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.eq_ignore_ascii_case("transfer-encoding")
+                && value.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+    let body = &response[header_end + 4..];
+    let body = if chunked { decode_chunked(body).ok_or_else(invalid)? } else { body.to_vec() };
+
+    Ok(Response {
+        status,
+        body: String::from_utf8(body).map_err(|_| invalid())?
+    })
+}
+
+fn decode_chunked(mut body: &[u8]) -> Option<Vec<u8>> {
+    let mut result = Vec::new();
+
+    loop {
+        let line_end = body.windows(2).position(|window| window == b"\r\n")?;
+        let size = std::str::from_utf8(&body[..line_end]).ok()?;
+        let size = size.split(';').next()?.trim();
+        let size = usize::from_str_radix(size, 16).ok()?;
+        body = &body[line_end + 2..];
+
+        if size == 0 {
+            return Some(result);
+        }
+
+        result.extend_from_slice(body.get(..size)?);
+        body = body.get(size + 2..)?;
+    }
+}
+
+fn send_with_curl(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>
+) -> Result<Response, String> {
+    let mut command = Command::new("curl");
+    // Headers go through stdin so that the session cookie doesn't show up in
+    // the process list.
+    command.args(["--silent", "--show-error", "--request", method, "--header", "@-"])
+        .args(["--max-time", &TIMEOUT.as_secs().to_string()])
+        .args(["--write-out", "\n%{http_code}", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    if let Some(body) = body {
+        command.args(["--data-binary", body]);
+    }
+
+    let mut child = command.spawn()
+        .map_err(|error| format!("cannot run curl: {}", error))?;
+    let header_lines: String = headers.iter()
+        .map(|(name, value)| format!("{}: {}\n", name, value))
+        .collect();
+    child.stdin.take().unwrap().write_all(header_lines.as_bytes())
+        .map_err(|error| format!("cannot run curl: {}", error))?;
+    let output = child.wait_with_output()
+        .map_err(|error| format!("cannot run curl: {}", error))?;
+
+    if !output.status.success() {
+        return Err(format!("curl: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    let output = String::from_utf8(output.stdout)
+        .map_err(|_| "Invalid HTTP response".to_owned())?;
+    let (body, status) = output.rsplit_once('\n')
+        .ok_or("Invalid HTTP response")?;
+
+    Ok(Response {
+        status: status.parse().map_err(|_| "Invalid HTTP response".to_owned())?,
+        body: body.to_owned()
+    })
+}
+
+#[test]
+fn test_parse_response() {
+    assert_eq!(
+        parse_response(b"HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\n1 2\n"),
+        Ok(Response { status: 200, body: "1 2\n".to_owned() })
+    );
+    assert_eq!(
+        parse_response(
+            b"HTTP/1.1 404 Not Found\r\nTransfer-Encoding: chunked\r\n\r\n\
+              4\r\nNot \r\n6;x=y\r\nfound.\r\n0\r\n\r\n"
+        ),
+        Ok(Response { status: 404, body: "Not found.".to_owned() })
+    );
+    assert!(parse_response(b"HTTP/1.1 200 OK\r\n").is_err());
+    assert!(parse_response(b"garbage\r\n\r\n").is_err());
+    assert!(parse_response(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n9\r\nab").is_err());
+}
+
+// Serves the given raw responses to consecutive connections on a local port,
+// and returns the base URL with a handle to the requests that were received.
+#[cfg(test)]
+pub fn serve(responses: Vec<String>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        responses.into_iter()
+            .map(|response| {
+                let (mut stream, _) = listener.accept().unwrap();
+                let request = read_request(&mut stream);
+                stream.write_all(response.as_bytes()).unwrap();
+                request
+            })
+            .collect()
+    });
+    (url, handle)
+}
+
+#[cfg(test)]
+fn read_request(stream: &mut TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+
+    loop {
+        let n = stream.read(&mut buffer).unwrap();
+        request.extend_from_slice(&buffer[..n]);
+        let text = String::from_utf8_lossy(&request).into_owned();
+
+        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+            let length = head.lines()
+                .find_map(|line| line.strip_prefix("Content-Length: "))
+                .map(|length| length.parse::<usize>().unwrap())
+                .unwrap_or(0);
+
+            if body.len() >= length || n == 0 {
+                return text;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_send_plain() {
+    let (url, server) = serve(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok".to_owned()
+    ]);
+    let response = send("POST", &format!("{}/answer", url), &[("Cookie", "session=abc")], Some("a=1"));
+    assert_eq!(response, Ok(Response { status: 200, body: "ok".to_owned() }));
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("POST /answer HTTP/1.1\r\n"));
+    assert!(requests[0].contains("\r\nCookie: session=abc\r\n"));
+    assert!(requests[0].ends_with("\r\nContent-Length: 3\r\n\r\na=1"));
+}
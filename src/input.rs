@@ -0,0 +1,81 @@
+use std::io::Read;
+use std::path::PathBuf;
+
+pub const DEFAULT_DIRECTORY: &str = "problem_inputs";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Input {
+    // Looks up <directory>/<day>.txt
+    Directory(PathBuf),
+    File(PathBuf),
+    Stdin,
+    Text(String)
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self::Directory(PathBuf::from(DEFAULT_DIRECTORY))
+    }
+}
+
+impl Input {
+    pub fn read(&self, day: u32) -> Result<String, String> {
+        match self {
+            Self::Directory(_) | Self::File(_) => {
+                let path = self.path(day).unwrap();
+                std::fs::read_to_string(&path).map_err(|error| {
+                    format!("cannot read {}: {}", path.display(), error)
+                })
+            }
+            Self::Stdin => {
+                let mut result = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut result)
+                    .map_err(|error| format!("cannot read stdin: {}", error))?;
+                Ok(result)
+            }
+            Self::Text(text) => Ok(text.clone())
+        }
+    }
+
+    pub fn path(&self, day: u32) -> Option<PathBuf> {
+        match self {
+            Self::Directory(directory) => {
+                Some(directory.join(format!("{}.txt", day)))
+            }
+            Self::File(path) => Some(path.clone()),
+            Self::Stdin | Self::Text(_) => None
+        }
+    }
+
+    // Sources that can only provide one day's input.
+    pub fn is_single_day(&self) -> bool {
+        !matches!(self, Self::Directory(_))
+    }
+}
+
+#[test]
+fn test_path() {
+    assert_eq!(
+        Input::default().path(5),
+        Some(PathBuf::from("problem_inputs/5.txt"))
+    );
+    assert_eq!(
+        Input::Directory(PathBuf::from("/tmp/inputs")).path(12),
+        Some(PathBuf::from("/tmp/inputs/12.txt"))
+    );
+    assert_eq!(
+        Input::File(PathBuf::from("mine.txt")).path(3),
+        Some(PathBuf::from("mine.txt"))
+    );
+    assert_eq!(Input::Stdin.path(3), None);
+}
+
+#[test]
+fn test_read() {
+    let input = Input::Text("Time: 7\nDistance: 9".to_owned());
+    assert_eq!(input.read(6), Ok("Time: 7\nDistance: 9".to_owned()));
+
+    let missing = Input::Directory(PathBuf::from("does/not/exist"));
+    assert!(missing.read(1).is_err());
+}
@@ -0,0 +1,286 @@
+// Half-open intervals of u64, wide enough for any u32 start plus a u32 length.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: u64,
+    pub end: u64
+}
+
+impl Interval {
+    pub fn new(start: u64, end: u64) -> Self {
+        assert!(start <= end, "interval ends at {} before starting at {}", end, start);
+        Self { start, end }
+    }
+
+    pub fn with_length(start: u64, length: u64) -> Self {
+        Self::new(start, start + length)
+    }
+
+    pub fn len(self) -> u64 {
+        self.end - self.start
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn contains(self, value: u64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn overlaps(self, other: Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    // None rather than an empty interval when they don't overlap.
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        (start < end).then_some(Self { start, end })
+    }
+
+    // The non-empty parts of self before and after other.
+    pub fn difference(self, other: Self) -> Vec<Self> {
+        let before = Self { start: self.start, end: self.end.min(other.start) };
+        let after = Self { start: self.start.max(other.end), end: self.end };
+        [before, after].into_iter()
+            .filter(|part| part.start < part.end)
+            .collect()
+    }
+
+    // Moves the interval so that `from` would land on `to`.
+    pub fn shift(self, from: u64, to: u64) -> Self {
+        Self {
+            start: self.start - from + to,
+            end: self.end - from + to
+        }
+    }
+
+    // Cuts self along `others`, which must not overlap each other. Returns
+    // the parts inside each of them, with the index of the one they are in,
+    // and the parts outside all of them.
+    pub fn split(self, others: &[Self]) -> (Vec<(usize, Self)>, Vec<Self>) {
+        let mut inside = Vec::new();
+        let mut outside = vec![self];
+
+        for (i, &other) in others.iter().enumerate() {
+            inside.extend(outside.iter().filter_map(|part| part.intersection(other)).map(|part| (i, part)));
+            outside = outside.into_iter()
+                .flat_map(|part| part.difference(other))
+                .collect();
+        }
+
+        (inside, outside)
+    }
+}
+
+impl std::fmt::Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+// Sorted intervals, merged so that none overlap or touch, and none are
+// empty. Two sets of the same values are therefore equal.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RangeSet {
+    intervals: Vec<Interval>
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        self.intervals.push(interval);
+        self.intervals = coalesce(std::mem::take(&mut self.intervals));
+    }
+
+    // The number of values in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(|interval| interval.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        let i = self.intervals.partition_point(|interval| interval.end <= value);
+        self.intervals.get(i).is_some_and(|interval| interval.contains(value))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.intervals.iter().chain(&other.intervals).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.intervals.iter()
+            .flat_map(|&a| other.intervals.iter().filter_map(move |&b| a.intersection(b)))
+            .collect()
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.intervals.clone();
+
+        for &b in &other.intervals {
+            result = result.into_iter().flat_map(|a| a.difference(b)).collect();
+        }
+
+        result.into_iter().collect()
+    }
+}
+
+impl FromIterator<Interval> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(intervals: I) -> Self {
+        Self { intervals: coalesce(intervals.into_iter().collect()) }
+    }
+}
+
+impl std::fmt::Display for RangeSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let intervals: Vec<String> = self.intervals.iter().map(|interval| interval.to_string()).collect();
+        write!(f, "{{{}}}", intervals.join(", "))
+    }
+}
+
+// Sorts the intervals and merges those that overlap or touch, dropping empty
+// ones.
+pub fn coalesce(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.retain(|interval| !interval.is_empty());
+    intervals.sort();
+    let mut result: Vec<Interval> = Vec::new();
+
+    for interval in intervals {
+        match result.last_mut() {
+            Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+            _ => result.push(interval)
+        }
+    }
+
+    result
+}
+
+// Every interval within 0..6, to check operations against sets of values.
+#[cfg(test)]
+fn all_intervals() -> Vec<Interval> {
+    (0..=6).flat_map(|start| (start..=6).map(move |end| Interval::new(start, end))).collect()
+}
+
+#[cfg(test)]
+fn values(intervals: &[Interval]) -> Vec<u64> {
+    let mut result: Vec<u64> = intervals.iter().flat_map(|interval| interval.start..interval.end).collect();
+    result.sort();
+    result
+}
+
+#[test]
+fn test_interval_operations() {
+    for a in all_intervals() {
+        assert_eq!(a.len(), values(&[a]).len() as u64);
+
+        for b in all_intervals() {
+            let (a_values, b_values) = (values(&[a]), values(&[b]));
+            let common: Vec<u64> = a_values.iter().copied().filter(|n| b_values.contains(n)).collect();
+            let only_a: Vec<u64> = a_values.iter().copied().filter(|n| !b_values.contains(n)).collect();
+            let intersection: Vec<Interval> = a.intersection(b).into_iter().collect();
+            assert_eq!(values(&intersection), common, "{} & {}", a, b);
+            assert_eq!(a.overlaps(b), !common.is_empty(), "{} & {}", a, b);
+            assert_eq!(values(&a.difference(b)), only_a, "{} - {}", a, b);
+            assert!(a.difference(b).iter().all(|part| !part.is_empty()));
+        }
+    }
+
+    assert!(Interval::with_length(3, 2).contains(4));
+    assert!(!Interval::with_length(3, 2).contains(5));
+    assert_eq!(Interval::new(10, 15).shift(8, 100), Interval::new(102, 107));
+    assert_eq!(Interval::new(1, 4).to_string(), "[1, 4)");
+}
+
+#[test]
+fn test_split() {
+    let others = [Interval::new(2, 4), Interval::new(5, 6), Interval::new(8, 9)];
+
+    for a in all_intervals() {
+        let (inside, outside) = a.split(&others);
+        let mut parts: Vec<Interval> = inside.iter().map(|&(_, part)| part).chain(outside.iter().copied()).collect();
+        assert_eq!(values(&parts), values(&[a]), "{}", a);
+
+        for &(i, part) in &inside {
+            assert_eq!(part.intersection(others[i]), Some(part));
+        }
+
+        assert!(outside.iter().all(|part| others.iter().all(|&other| !part.overlaps(other))));
+        parts.sort();
+        assert!(parts.windows(2).all(|pair| pair[0].end <= pair[1].start), "{}", a);
+    }
+
+    assert_eq!(
+        Interval::new(0, 7).split(&others),
+        (
+            vec![(0, Interval::new(2, 4)), (1, Interval::new(5, 6))],
+            vec![Interval::new(0, 2), Interval::new(4, 5), Interval::new(6, 7)]
+        )
+    );
+}
+
+#[test]
+fn test_range_set() {
+    let sets: Vec<RangeSet> = all_intervals().chunks(3)
+        .map(|intervals| intervals.iter().copied().collect())
+        .collect();
+
+    for a in &sets {
+        assert!(a.intervals().windows(2).all(|pair| pair[0].end < pair[1].start), "{}", a);
+        assert!(a.intervals().iter().all(|interval| !interval.is_empty()));
+        assert_eq!(a.len(), values(a.intervals()).len() as u64);
+        assert_eq!(a.min(), values(a.intervals()).first().copied());
+        assert_eq!(a.is_empty(), values(a.intervals()).is_empty());
+
+        for n in 0..8 {
+            assert_eq!(a.contains(n), values(a.intervals()).contains(&n), "{} in {}", n, a);
+        }
+
+        for b in &sets {
+            let (a_values, b_values) = (values(a.intervals()), values(b.intervals()));
+            let mut union: Vec<u64> = a_values.iter().chain(&b_values).copied().collect();
+            union.sort();
+            union.dedup();
+            assert_eq!(values(a.union(b).intervals()), union, "{} | {}", a, b);
+            assert_eq!(
+                values(a.intersection(b).intervals()),
+                a_values.iter().copied().filter(|n| b_values.contains(n)).collect::<Vec<u64>>(),
+                "{} & {}",
+                a,
+                b
+            );
+            assert_eq!(
+                values(a.difference(b).intervals()),
+                a_values.iter().copied().filter(|n| !b_values.contains(n)).collect::<Vec<u64>>(),
+                "{} - {}",
+                a,
+                b
+            );
+        }
+    }
+}
+
+#[test]
+fn test_coalesce() {
+    let mut set: RangeSet = [Interval::new(5, 7), Interval::new(1, 3), Interval::new(4, 4)].into_iter().collect();
+    assert_eq!(set.to_string(), "{[1, 3), [5, 7)}");
+    set.insert(Interval::new(3, 5));
+    assert_eq!(set.intervals(), &[Interval::new(1, 7)]);
+    set.insert(Interval::new(0, 10));
+    assert_eq!(set.intervals(), &[Interval::new(0, 10)]);
+    assert_eq!(RangeSet::new().to_string(), "{}");
+    assert_eq!(coalesce(vec![Interval::new(2, 2)]), []);
+}
@@ -0,0 +1,33 @@
+pub mod bench;
+pub mod counter;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day7_part1;
+pub mod day7_part2;
+pub mod differential;
+pub mod error;
+pub mod fetch;
+pub mod fixture;
+pub mod fuzz;
+pub mod generate;
+pub mod grid;
+pub mod http;
+pub mod input;
+pub mod interval;
+pub mod manifest;
+pub mod parser;
+pub mod pool;
+pub mod registry;
+pub mod repl;
+pub mod report;
+pub mod rng;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod watch;
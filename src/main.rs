@@ -1,25 +1,323 @@
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7_part1;
-pub mod day7_part2;
-
-fn main() {
-    assert_eq!(day1::part1(), 54605);
-    assert_eq!(day1::part2(), 55429);
-    assert_eq!(day2::part1(), 2476);
-    assert_eq!(day2::part2(), 54911);
-    assert_eq!(day3::part1(), 543867);
-    assert_eq!(day3::part2(), 79613331);
-    assert_eq!(day4::part1(), 25571);
-    assert_eq!(day4::part2(), 8805731);
-    assert_eq!(day5::part1(), 318728750);
-    assert_eq!(day5::part2(), 37384986);
-    assert_eq!(day6::part1(), 170000);
-    assert_eq!(day6::part2(), 20537782);
-    assert_eq!(day7_part1::part1(), 251806792);
-    assert_eq!(day7_part2::part2(), 252113488);
+mod cli;
+
+use adventofcode2023::fetch::{self, Fetched};
+use adventofcode2023::manifest::{self, Manifest};
+use adventofcode2023::submit::{self, Attempt, History, Verdict};
+use adventofcode2023::{bench, fuzz, generate, registry, repl, report, runner, scaffold, watch};
+use cli::{
+    BenchOptions,
+    Command,
+    FetchOptions,
+    FuzzOptions,
+    GenerateOptions,
+    RunOptions,
+    Selection,
+    SubmitOptions
+};
+
+fn main() -> std::process::ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match cli::parse_args(&args, registry::available_days()) {
+        Ok(Command::Run(options)) => run(&options),
+        Ok(Command::Bench(options)) => bench(&options),
+        Ok(Command::NewDay(day)) => new_day(day),
+        Ok(Command::Fetch(options)) => fetch(&options),
+        Ok(Command::Submit(options)) => submit(&options),
+        Ok(Command::Watch(selection)) => watch(&selection),
+        Ok(Command::Repl(selection)) => repl(&selection),
+        Ok(Command::Generate(options)) => generate(&options),
+        Ok(Command::Fuzz(options)) => fuzz(&options),
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, cli::USAGE);
+            std::process::ExitCode::from(2)
+        }
+    }
+}
+
+fn run(options: &RunOptions) -> std::process::ExitCode {
+    let mut manifest = match Manifest::load(&options.manifest) {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            eprintln!("{}", error);
+            return std::process::ExitCode::FAILURE;
+        }
+    };
+    std::panic::set_hook(Box::new(|_| {}));
+    let results = runner::run(
+        &options.selection.days,
+        &options.selection.parts,
+        &options.selection.input,
+        options.jobs,
+        &mut manifest,
+        options.record,
+        options.explain
+    );
+    print!("{}", report::render(options.format, &results));
+    let mut failed = results.iter().any(|result| result.is_failure());
+
+    if options.record {
+        if let Err(error) = manifest.save(&options.manifest) {
+            eprintln!("{}", error);
+            failed = true;
+        }
+    }
+
+    if failed {
+        std::process::ExitCode::FAILURE
+    } else {
+        std::process::ExitCode::SUCCESS
+    }
+}
+
+fn bench(options: &BenchOptions) -> std::process::ExitCode {
+    std::panic::set_hook(Box::new(|_| {}));
+    let mut failed = false;
+    println!(
+        "{:<4} {:<5} {:<36} Solve min / median / p95",
+        "Day",
+        "Part",
+        "Parse min / median / p95"
+    );
+
+    for &day in &options.selection.days {
+        let solution = registry::find(day).unwrap();
+        let text = match options.selection.input.read(day) {
+            Ok(text) => text,
+            Err(error) => {
+                eprintln!("Day {}: {}", day, error);
+                failed = true;
+                continue;
+            }
+        };
+
+        for &part in &options.selection.parts {
+            let measurement = bench::measure(
+                solution,
+                &text,
+                part,
+                options.warmup,
+                options.iterations
+            );
+            let measurement = match measurement {
+                Ok(measurement) => measurement,
+                Err(error) => {
+                    eprintln!("Day {} part {}: error: {}\n", day, part, error);
+                    failed = true;
+                    continue;
+                }
+            };
+            println!(
+                "{:<4} {:<5} {:<36} {}",
+                day,
+                part,
+                measurement.parse.to_string(),
+                measurement.solve
+            );
+        }
+    }
+
+    if failed {
+        std::process::ExitCode::FAILURE
+    } else {
+        std::process::ExitCode::SUCCESS
+    }
+}
+
+fn new_day(day: u32) -> std::process::ExitCode {
+    match scaffold::create(std::path::Path::new("."), day) {
+        Ok(paths) => {
+            for path in paths {
+                println!("Wrote {}", path.display());
+            }
+
+            std::process::ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::ExitCode::FAILURE
+        }
+    }
+}
+
+fn fetch(options: &FetchOptions) -> std::process::ExitCode {
+    let result = fetch::session(&options.session_file).and_then(|session| {
+        fetch::fetch(&options.base_url, options.day, &session, &options.directory)
+    });
+
+    match result {
+        Ok(Fetched::Downloaded(path)) => {
+            println!("Day {}: downloaded to {}", options.day, path.display());
+            std::process::ExitCode::SUCCESS
+        }
+        Ok(Fetched::Cached(path)) => {
+            println!("Day {}: already in {}", options.day, path.display());
+            std::process::ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("Day {}: {}", options.day, error);
+            std::process::ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit(options: &SubmitOptions) -> std::process::ExitCode {
+    match try_submit(options) {
+        Ok(Verdict::Correct) => std::process::ExitCode::SUCCESS,
+        Ok(_) => std::process::ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("Day {} part {}: {}", options.day, options.part, error);
+            std::process::ExitCode::FAILURE
+        }
+    }
+}
+
+fn try_submit(options: &SubmitOptions) -> Result<Verdict, String> {
+    let (day, part) = (options.day, options.part);
+    let mut history = History::load(&options.history)?;
+    let mut manifest = Manifest::load(&options.manifest)?;
+    let text = options.input.read(day)?;
+    std::panic::set_hook(Box::new(|_| {}));
+    let answer = runner::solve(registry::find(day).unwrap(), &text, part)?;
+    history.check(day, part, answer)?;
+
+    let session = fetch::session(&options.session_file)?;
+    let verdict = submit::submit(&options.base_url, day, part, answer, &session)?;
+    println!("Day {} part {}: {} is {}", day, part, answer, verdict);
+    history.record(Attempt { day, part, answer, verdict });
+    history.save(&options.history)?;
+
+    if verdict == Verdict::Correct {
+        manifest.record(day, part, manifest::input_hash(&text), answer);
+        manifest.save(&options.manifest)?;
+    }
+
+    Ok(verdict)
+}
+
+fn watch(selection: &Selection) -> std::process::ExitCode {
+    let day = selection.days[0];
+    let source_directory = std::path::Path::new("src");
+    let mut stamps = watch::Stamps::new();
+    let mut previous: Vec<watch::PartSummary> = Vec::new();
+
+    loop {
+        let current = watch::stamps(source_directory, day, &selection.input);
+
+        if current == stamps {
+            std::thread::sleep(watch::POLL_INTERVAL);
+            continue;
+        }
+
+        // Editors often write in several steps, so let the files settle.
+        std::thread::sleep(watch::POLL_INTERVAL);
+        stamps = watch::stamps(source_directory, day, &selection.input);
+        println!("=== Day {}: running tests", day);
+
+        match watch::run_tests(day) {
+            Ok(true) => {}
+            Ok(false) => println!("=== Day {}: tests failed", day),
+            Err(error) => {
+                eprintln!("{}", error);
+                return std::process::ExitCode::FAILURE;
+            }
+        }
+
+        println!("=== Day {}: running input", day);
+
+        match watch::run_day(day, &selection.parts, &selection.input) {
+            Ok(summaries) => {
+                for summary in &summaries {
+                    let before = previous.iter().find(|before| before.part == summary.part);
+                    let change = summary.change(before);
+
+                    match &summary.outcome {
+                        Ok((answer, status)) => {
+                            println!("Day {} part {}: {} ({}, {})", day, summary.part, answer, status, change);
+                        }
+                        Err(error) => {
+                            println!("Day {} part {}: error ({}): {}", day, summary.part, change, error);
+                        }
+                    }
+                }
+
+                previous = summaries;
+            }
+            Err(error) => println!("Day {}: {}", day, error)
+        }
+
+        println!("=== Day {}: watching for changes", day);
+    }
+}
+
+fn repl(selection: &Selection) -> std::process::ExitCode {
+    use std::io::{BufRead, Write};
+
+    let day = selection.days[0];
+    let mut session = match repl::Session::new(day, selection.parts.clone(), selection.input.clone()) {
+        Ok(session) => session,
+        Err(error) => {
+            eprintln!("{}", error);
+            return std::process::ExitCode::FAILURE;
+        }
+    };
+    let mut lines = std::io::stdin().lock().lines();
+    println!("Day {} parsed, type help for the commands", day);
+
+    loop {
+        print!("{}", session.prompt());
+        std::io::stdout().flush().unwrap();
+
+        let Some(Ok(line)) = lines.next() else {
+            println!();
+            return std::process::ExitCode::SUCCESS;
+        };
+
+        match session.execute(&line) {
+            Ok(repl::Reply::Output(output)) if output.is_empty() => {}
+            Ok(repl::Reply::Output(output)) => println!("{}", output),
+            Ok(repl::Reply::Quit) => return std::process::ExitCode::SUCCESS,
+            Err(error) => println!("Error: {}", error)
+        }
+    }
+}
+
+fn generate(options: &GenerateOptions) -> std::process::ExitCode {
+    match generate::generate(options.day, options.seed, options.size) {
+        Some(input) => {
+            print!("{}", input);
+            std::process::ExitCode::SUCCESS
+        }
+        None => {
+            eprintln!("Day {} has no generator", options.day);
+            std::process::ExitCode::FAILURE
+        }
+    }
+}
+
+fn fuzz(options: &FuzzOptions) -> std::process::ExitCode {
+    std::panic::set_hook(Box::new(|_| {}));
+    let mut failed = false;
+
+    for &day in &options.days {
+        if generate::generator(day).is_none() {
+            eprintln!("Day {} has no generator", day);
+            failed = true;
+            continue;
+        }
+
+        match fuzz::fuzz(day, options.seed, options.iterations) {
+            Ok(()) => println!("Day {}: no panic in {} inputs", day, options.iterations),
+            Err(crash) => {
+                println!("{}", crash);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        std::process::ExitCode::FAILURE
+    } else {
+        std::process::ExitCode::SUCCESS
+    }
 }
@@ -0,0 +1,156 @@
+use crate::solution::Answer;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub const DEFAULT_PATH: &str = "problem_inputs/answers.txt";
+
+const HEADER: &str = "# day part input-hash answer";
+
+// Confirmed answers, keyed by day, part and a hash of the input they were
+// computed from, so that several people's inputs can share one manifest.
+#[derive(Debug, Default, PartialEq)]
+pub struct Manifest {
+    answers: BTreeMap<(u32, u32, u64), Answer>
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: Answer },
+    Unknown
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail { expected } => write!(f, "FAIL, expected {}", expected),
+            Self::Unknown => write!(f, "unknown")
+        }
+    }
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text)
+                .map_err(|error| format!("{}: {}", path.display(), error)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                Ok(Self::default())
+            }
+            Err(error) => {
+                Err(format!("cannot read {}: {}", path.display(), error))
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_string())
+            .map_err(|error| format!("cannot write {}: {}", path.display(), error))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut answers = BTreeMap::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_ascii_whitespace().collect();
+            let invalid = || format!("invalid entry on line {}: {:?}", i + 1, line);
+
+            if fields.len() != 4 {
+                return Err(invalid());
+            }
+
+            let day = fields[0].parse::<u32>().map_err(|_| invalid())?;
+            let part = fields[1].parse::<u32>().map_err(|_| invalid())?;
+            let hash = u64::from_str_radix(fields[2], 16).map_err(|_| invalid())?;
+            let answer = fields[3].parse::<Answer>().map_err(|_| invalid())?;
+            answers.insert((day, part, hash), answer);
+        }
+
+        Ok(Self { answers })
+    }
+
+    pub fn expected(&self, day: u32, part: u32, hash: u64) -> Option<Answer> {
+        self.answers.get(&(day, part, hash)).copied()
+    }
+
+    pub fn check(&self, day: u32, part: u32, hash: u64, answer: Answer) -> Status {
+        match self.expected(day, part, hash) {
+            Some(expected) if expected == answer => Status::Pass,
+            Some(expected) => Status::Fail { expected },
+            None => Status::Unknown
+        }
+    }
+
+    pub fn record(&mut self, day: u32, part: u32, hash: u64, answer: Answer) {
+        self.answers.insert((day, part, hash), answer);
+    }
+}
+
+impl std::fmt::Display for Manifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+
+        for ((day, part, hash), answer) in &self.answers {
+            writeln!(f, "{} {} {:016x} {}", day, part, hash, answer)?;
+        }
+
+        Ok(())
+    }
+}
+
+// 64-bit FNV-1a, which unlike std's DefaultHasher is stable across Rust
+// releases.
+pub fn input_hash(input: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    for &byte in input.as_bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash
+}
+
+#[test]
+fn test_input_hash() {
+    assert_eq!(input_hash(""), 0xcbf29ce484222325);
+    assert_eq!(input_hash("a"), 0xaf63dc4c8601ec8c);
+    assert_ne!(input_hash("1abc2\n"), input_hash("1abc2"));
+}
+
+#[test]
+fn test_check_and_record() {
+    let mut manifest = Manifest::parse(
+        "# day part input-hash answer\n\
+         5 1 00000000000000ff 35\n"
+    ).unwrap();
+    assert_eq!(manifest.check(5, 1, 0xff, 35), Status::Pass);
+    assert_eq!(manifest.check(5, 1, 0xff, 36), Status::Fail { expected: 35 });
+    assert_eq!(manifest.check(5, 2, 0xff, 46), Status::Unknown);
+    assert_eq!(manifest.check(5, 1, 0xfe, 35), Status::Unknown);
+
+    manifest.record(5, 2, 0xff, 46);
+    manifest.record(5, 1, 0xff, 36);
+    assert_eq!(manifest.check(5, 1, 0xff, 36), Status::Pass);
+    assert_eq!(
+        manifest.to_string(),
+        "# day part input-hash answer\n\
+         5 1 00000000000000ff 36\n\
+         5 2 00000000000000ff 46\n"
+    );
+    assert_eq!(Manifest::parse(&manifest.to_string()), Ok(manifest));
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(Manifest::parse("5 1 ff").is_err());
+    assert!(Manifest::parse("5 1 zz 35").is_err());
+    assert!(Manifest::parse("five 1 ff 35").is_err());
+}
@@ -0,0 +1,240 @@
+use crate::error::{self, LineError, ParseError};
+use std::str::FromStr;
+
+// Small parsers over one line at a time. They return slices of the line
+// rather than copies, so that errors built from them point at the right
+// column; `expected` describes the shape the caller wanted, e.g.
+// "Game <id>: <sets>", and ends up quoted in the message.
+
+// Parses every line, locating errors on the line they come from.
+pub fn lines<'a, T>(
+    lines: impl IntoIterator<Item = &'a str>,
+    day: u32,
+    mut parse: impl FnMut(&'a str) -> Result<T, LineError>
+) -> Result<Vec<T>, ParseError> {
+    lines.into_iter()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|error| error.locate(day, i, line)))
+        .collect()
+}
+
+// What follows `label`, e.g. the id after "Game ".
+pub fn label<'a>(s: &'a str, label: &str, expected: &str) -> Result<&'a str, LineError> {
+    s.strip_prefix(label)
+        .ok_or_else(|| LineError::whole(s, format!("expected {:?}", expected)))
+}
+
+// The two sides of the first `separator`, e.g. "Game 1" and " 3 red".
+pub fn split_once<'a>(
+    s: &'a str,
+    separator: &str,
+    expected: &str
+) -> Result<(&'a str, &'a str), LineError> {
+    s.split_once(separator)
+        .ok_or_else(|| LineError::whole(s, format!("expected {:?}", expected)))
+}
+
+// The items between separators, trimmed and parsed by `item`.
+pub fn separated<'a, T>(
+    s: &'a str,
+    separator: char,
+    item: impl FnMut(&'a str) -> Result<T, LineError>
+) -> Result<Vec<T>, LineError> {
+    s.split(separator)
+        .map(str::trim)
+        .map(item)
+        .collect()
+}
+
+// Exactly N trimmed items between separators.
+pub fn split_n<'a, const N: usize>(
+    s: &'a str,
+    separator: char,
+    expected: &str
+) -> Result<[&'a str; N], LineError> {
+    exactly(s, s.split(separator).map(str::trim).collect(), expected)
+}
+
+// Exactly N words, separated by any amount of whitespace.
+pub fn fields<'a, const N: usize>(s: &'a str, expected: &str) -> Result<[&'a str; N], LineError> {
+    exactly(s, s.split_ascii_whitespace().collect(), expected)
+}
+
+fn exactly<'a, const N: usize>(
+    s: &'a str,
+    parts: Vec<&'a str>,
+    expected: &str
+) -> Result<[&'a str; N], LineError> {
+    parts.try_into()
+        .map_err(|_| LineError::whole(s, format!("expected {:?}", expected)))
+}
+
+// `what` names the number for the error, e.g. "a game id".
+pub fn integer<T: FromStr>(token: &str, what: &str) -> Result<T, LineError> {
+    token.parse()
+        .map_err(|_| LineError::new(token, format!("expected {}", what)))
+}
+
+// Numbers separated by any amount of whitespace.
+pub fn integers<T: FromStr>(s: &str, what: &str) -> Result<Vec<T>, LineError> {
+    s.split_ascii_whitespace()
+        .map(|token| integer(token, what))
+        .collect()
+}
+
+// The value named by the token, e.g. a color out of its name. `what` names the
+// kind of token for the error.
+pub fn one_of<T: Copy>(token: &str, options: &[(&str, T)], what: &str) -> Result<T, LineError> {
+    options.iter()
+        .find(|&&(name, _)| name == token)
+        .map(|&(_, value)| value)
+        .ok_or_else(|| LineError::new(token, format!("unknown {} {:?}", what, token)))
+}
+
+// A block of "<key> <value>" lines, with exactly one line per key, in order,
+// e.g. "Time: 7 15" then "Distance: 9 40". Returns each line with its value.
+pub fn key_values<'a>(
+    input: &'a str,
+    day: u32,
+    keys: &[&str],
+    value: &str
+) -> Result<Vec<(&'a str, &'a str)>, ParseError> {
+    let lines: Vec<&str> = input.lines().collect();
+
+    if let Some(&line) = lines.get(keys.len()) {
+        return Err(LineError::whole(line, "unexpected line").locate(day, keys.len(), line));
+    }
+
+    keys.iter()
+        .enumerate()
+        .map(|(i, key)| {
+            let expected = format!("{} {}", key, value);
+            let line = *lines.get(i)
+                .ok_or_else(|| error::missing(day, lines.len(), format!("expected {:?}", expected)))?;
+            let value = label(line, key, &expected).map_err(|error| error.locate(day, i, line))?;
+            Ok((line, value))
+        })
+        .collect()
+}
+
+// Lines between blank lines.
+#[derive(Debug, PartialEq)]
+pub struct Section<'a> {
+    // The 0-based index of the first line in the input. An empty section,
+    // between two blank lines in a row, starts at the second one.
+    pub start: usize,
+    pub lines: Vec<&'a str>
+}
+
+// Splits the lines at each blank one. A blank line at the very end doesn't
+// start another section.
+pub fn sections<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<Section<'a>> {
+    let mut result = Vec::new();
+    let mut current = Section { start: 0, lines: Vec::new() };
+
+    for (i, line) in lines.into_iter().enumerate() {
+        if line.is_empty() {
+            let next = Section { start: i + 1, lines: Vec::new() };
+            let mut done = std::mem::replace(&mut current, next);
+
+            if done.lines.is_empty() {
+                done.start = i;
+            }
+
+            result.push(done);
+        } else {
+            current.lines.push(line);
+        }
+    }
+
+    if !current.lines.is_empty() {
+        result.push(current);
+    }
+
+    result
+}
+
+// The column and width of the error on the line, to check where it points.
+#[cfg(test)]
+fn position(error: LineError, line: &str) -> (usize, usize) {
+    let error = error.locate(0, 0, line);
+    (error.column, error.width)
+}
+
+#[test]
+fn test_labels_and_splits() {
+    let line = "Game 12: 3 red";
+    let (header, rest) = split_once(line, ":", "Game <id>: <sets>").unwrap();
+    assert_eq!((header, rest), ("Game 12", " 3 red"));
+    assert_eq!(label(header, "Game ", "Game <id>"), Ok("12"));
+
+    let error = label(line, "Card ", "Card <id>").unwrap_err();
+    assert_eq!(position(error.clone(), line), (1, 14));
+    assert_eq!(error.locate(0, 0, line).message, "expected \"Card <id>\"");
+    assert_eq!(position(split_once(line, "|", "a | b").unwrap_err(), line), (1, 14));
+
+    assert_eq!(fields::<2>(" 32T3K   765 ", "<hand> <bid>"), Ok(["32T3K", "765"]));
+    assert!(fields::<2>("32T3K 765 1", "<hand> <bid>").is_err());
+    assert_eq!(split_n::<2>("1 2 | 3", '|', "<a> | <b>"), Ok(["1 2", "3"]));
+    assert!(split_n::<2>("1 | 2 | 3", '|', "<a> | <b>").is_err());
+}
+
+#[test]
+fn test_numbers() {
+    let line = "seeds: 79 14 x 13";
+    assert_eq!(integers::<u32>(&line[6..12], "a seed id"), Ok(vec![79, 14]));
+    let error = integers::<u32>(line.strip_prefix("seeds:").unwrap(), "a seed id").unwrap_err();
+    assert_eq!(position(error, line), (14, 1));
+    assert_eq!(integers::<u64>("", "a number"), Ok(Vec::new()));
+    assert!(integer::<u32>("4294967296", "a number").is_err());
+
+    let line = "1 red,, 2 blue";
+    let items = separated(line, ',', |item| fields::<2>(item, "<quantity> <color>"));
+    assert_eq!(position(items.unwrap_err(), line), (7, 0));
+
+    let colors = [("red", 0), ("green", 1), ("blue", 2)];
+    assert_eq!(one_of("blue", &colors, "color"), Ok(2));
+    let line = "3 purple";
+    let error = one_of(&line[2..], &colors, "color").unwrap_err();
+    assert_eq!(position(error.clone(), line), (3, 6));
+    assert_eq!(error.locate(0, 0, line).message, "unknown color \"purple\"");
+}
+
+#[test]
+fn test_blocks() {
+    let keys = ["Time:", "Distance:"];
+    assert_eq!(
+        key_values("Time: 7\nDistance: 9", 6, &keys, "<numbers>"),
+        Ok(vec![("Time: 7", " 7"), ("Distance: 9", " 9")])
+    );
+
+    let error = key_values("Time: 7", 6, &keys, "<numbers>").unwrap_err();
+    assert_eq!((error.line, error.message.as_str()), (2, "expected \"Distance: <numbers>\""));
+    let error = key_values("Time: 7\nDistance: 9\nSpeed: 1", 6, &keys, "<numbers>").unwrap_err();
+    assert_eq!((error.line, error.column, error.width), (3, 1, 8));
+    let error = key_values("Time: 7\nDistnace: 9", 6, &keys, "<numbers>").unwrap_err();
+    assert_eq!((error.line, error.column, error.width), (2, 1, 11));
+}
+
+#[test]
+fn test_lines() {
+    let parse = |input: &str| lines(input.lines(), 1, |line| integer::<u32>(line, "a number"));
+    assert_eq!(parse("1\n2"), Ok(vec![1, 2]));
+    let error = parse("1\n2\n x").unwrap_err();
+    assert_eq!((error.line, error.column, error.width), (3, 1, 2));
+}
+
+#[test]
+fn test_sections() {
+    assert_eq!(
+        sections("a\nb\n\nc\n\n\nd\n".lines()),
+        [
+            Section { start: 0, lines: vec!["a", "b"] },
+            Section { start: 3, lines: vec!["c"] },
+            Section { start: 5, lines: Vec::new() },
+            Section { start: 6, lines: vec!["d"] }
+        ]
+    );
+    assert_eq!(sections("a\n\n".lines()), [Section { start: 0, lines: vec!["a"] }]);
+    assert_eq!(sections("".lines()), []);
+}
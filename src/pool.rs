@@ -0,0 +1,66 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Applies `f` to every item on up to `jobs` threads. Threads pick the next
+// pending item as soon as they're done with one, so a slow item doesn't hold
+// back the others, and the results come back in the order of `items`.
+pub fn map<T, R, F>(jobs: usize, items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync
+{
+    assert!(jobs > 0);
+
+    if jobs == 1 || items.len() <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.min(items.len()))
+            .map(|_| scope.spawn(|| {
+                let mut done = Vec::new();
+
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+
+                    match items.get(i) {
+                        Some(item) => done.push((i, f(item))),
+                        None => break done
+                    }
+                }
+            }))
+            .collect();
+
+        for worker in workers {
+            for (i, result) in worker.join().unwrap() {
+                results[i] = Some(result);
+            }
+        }
+    });
+
+    results.into_iter()
+        .map(|result| result.unwrap())
+        .collect()
+}
+
+#[test]
+fn test_map_keeps_order() {
+    let items: Vec<u64> = (0..100).collect();
+
+    for jobs in [1, 2, 8, 200] {
+        let results = map(jobs, &items, |&n| {
+            std::thread::sleep(std::time::Duration::from_micros((100 - n) * 10));
+            n * n
+        });
+        assert_eq!(results, items.iter().map(|n| n * n).collect::<Vec<u64>>());
+    }
+}
+
+#[test]
+fn test_map_empty() {
+    let items: [u32; 0] = [];
+    assert_eq!(map(4, &items, |&n| n), Vec::<u32>::new());
+}
@@ -0,0 +1,39 @@
+use crate::solution::AnySolution;
+use crate::{day1, day2, day3, day4, day5, day6, day7};
+
+pub const SOLUTIONS: &[&dyn AnySolution] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7
+];
+
+// Days fresh out of new-day, whose parts aren't solved yet. Until a day comes
+// off this list, it isn't expected to have examples, a generator or repl
+// commands.
+pub const UNSOLVED: &[u32] = &[];
+
+pub fn find(day: u32) -> Option<&'static dyn AnySolution> {
+    SOLUTIONS.iter()
+        .find(|solution| solution.day() == day)
+        .copied()
+}
+
+pub fn available_days() -> u32 {
+    SOLUTIONS.iter()
+        .map(|solution| solution.day())
+        .max()
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_days_are_unique_and_contiguous() {
+    let days: Vec<u32> = SOLUTIONS.iter()
+        .map(|solution| solution.day())
+        .collect();
+    assert_eq!(days, (1..=available_days()).collect::<Vec<u32>>());
+    assert!(UNSOLVED.iter().all(|&day| find(day).is_some()));
+}
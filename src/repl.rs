@@ -0,0 +1,376 @@
+use crate::input::Input;
+use crate::registry;
+use crate::runner;
+use crate::solution::{Answer, AnySolution};
+use crate::{day1, day2, day3, day4, day5, day6, day7, day7_part1, day7_part2};
+use std::any::Any;
+use std::str::FromStr;
+
+// A command over the parsed input of one day, which downcasts the model it
+// gets to that day's.
+pub struct DayCommand {
+    pub name: &'static str,
+    pub arguments: &'static str,
+    pub help: &'static str,
+    pub run: fn(&dyn Any, &[&str]) -> Result<String, String>
+}
+
+const GENERAL_HELP: &str = "  solve [1|2]          solve the selected parts, or the given one
+  reload               read and parse the input again
+  help                 show this help
+  quit                 leave the REPL";
+
+pub fn day_commands(day: u32) -> &'static [DayCommand] {
+    match day {
+        1 => &[DayCommand {
+            name: "line",
+            arguments: "<n>",
+            help: "the digits found on line <n> by each part",
+            run: day1_line
+        }],
+        2 => &[DayCommand {
+            name: "game",
+            arguments: "<id>",
+            help: "the cubes a game needs, and whether it's possible",
+            run: day2_game
+        }],
+        3 => &[
+            DayCommand {
+                name: "gears",
+                arguments: "",
+                help: "every star with the numbers around it",
+                run: day3_gears
+            },
+            DayCommand {
+                name: "numbers",
+                arguments: "",
+                help: "every number, and whether it's a part number",
+                run: day3_numbers
+            }
+        ],
+        4 => &[DayCommand {
+            name: "card",
+            arguments: "<id>",
+            help: "the winning numbers of a card and the copies it wins",
+            run: day4_card
+        }],
+        5 => &[DayCommand {
+            name: "seed",
+            arguments: "<id>",
+            help: "the path of a seed through every map",
+            run: day5_seed
+        }],
+        6 => &[DayCommand {
+            name: "race",
+            arguments: "<n|kerned>",
+            help: "the ways to win race <n>, or the race of part 2",
+            run: day6_race
+        }],
+        7 => &[DayCommand {
+            name: "bid",
+            arguments: "<n>",
+            help: "the hand type and rank of the bid on line <n> in both parts",
+            run: day7_bid
+        }],
+        _ => &[]
+    }
+}
+
+pub enum Reply {
+    Output(String),
+    Quit
+}
+
+pub struct Session {
+    day: u32,
+    parts: Vec<u32>,
+    input: Input,
+    solution: &'static dyn AnySolution,
+    text: String,
+    parsed: Box<dyn Any>
+}
+
+impl Session {
+    pub fn new(day: u32, parts: Vec<u32>, input: Input) -> Result<Self, String> {
+        let solution = registry::find(day).ok_or_else(|| format!("Day {} is not available", day))?;
+        let text = input.read(day)?;
+        let parsed = solution.parse_any(&text).map_err(|error| error.to_string())?;
+        Ok(Self { day, parts, input, solution, text, parsed })
+    }
+
+    pub fn prompt(&self) -> String {
+        format!("day {}> ", self.day)
+    }
+
+    pub fn execute(&mut self, line: &str) -> Result<Reply, String> {
+        let words: Vec<&str> = line.split_ascii_whitespace().collect();
+        let Some((&name, arguments)) = words.split_first() else {
+            return Ok(Reply::Output(String::new()));
+        };
+
+        let output = match name {
+            "quit" | "exit" => {
+                return Ok(Reply::Quit);
+            }
+            "help" => self.help(),
+            "reload" => {
+                *self = Self::new(self.day, self.parts.clone(), self.input.clone())?;
+                format!("Reloaded {} lines", self.text.lines().count())
+            }
+            "solve" => {
+                let parts = match arguments {
+                    [] => self.parts.clone(),
+                    [part] => vec![parse_argument(part, "part").and_then(|part| match part {
+                        1 | 2 => Ok(part),
+                        _ => Err(format!("Invalid part {}, expected 1 or 2", part))
+                    })?],
+                    _ => {
+                        return Err("Usage: solve [1|2]".to_owned());
+                    }
+                };
+                parts.iter()
+                    .map(|&part| match runner::solve(self.solution, &self.text, part) {
+                        Ok(answer) => format!("Part {}: {}", part, answer),
+                        Err(error) => format!("Part {}: {}", part, error)
+                    })
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            }
+            name => {
+                let command = day_commands(self.day).iter()
+                    .find(|command| command.name == name)
+                    .ok_or_else(|| format!("Unknown command {:?}, try help", name))?;
+                (command.run)(self.parsed.as_ref(), arguments)?
+            }
+        };
+
+        Ok(Reply::Output(output))
+    }
+
+    fn help(&self) -> String {
+        let mut lines = vec![format!("Day {} commands:", self.day)];
+
+        for command in day_commands(self.day) {
+            let usage = format!("{} {}", command.name, command.arguments);
+            lines.push(format!("  {:<20} {}", usage.trim_end(), command.help));
+        }
+
+        lines.push("Other commands:".to_owned());
+        lines.push(GENERAL_HELP.to_owned());
+        lines.join("\n")
+    }
+}
+
+fn model<T: 'static>(parsed: &dyn Any) -> &T {
+    parsed.downcast_ref::<T>().expect("Parsed model belongs to another day")
+}
+
+fn parse_argument<T: FromStr>(s: &str, name: &str) -> Result<T, String> {
+    s.parse::<T>().map_err(|_| format!("Invalid {} {:?}", name, s))
+}
+
+// The single argument of a command.
+fn argument<T: FromStr>(arguments: &[&str], name: &str) -> Result<T, String> {
+    match arguments {
+        [argument] => parse_argument(argument, name),
+        _ => Err(format!("Expected a {}", name))
+    }
+}
+
+fn no_arguments(arguments: &[&str]) -> Result<(), String> {
+    match arguments {
+        [] => Ok(()),
+        [argument, ..] => Err(format!("Unexpected argument {:?}", argument))
+    }
+}
+
+// Finds the item on the 1-based line `n`.
+fn nth<'a, T>(items: &'a [T], n: usize, name: &str) -> Result<&'a T, String> {
+    n.checked_sub(1)
+        .and_then(|i| items.get(i))
+        .ok_or_else(|| format!("No {} {}, there are {}", name, n, items.len()))
+}
+
+fn day1_line(parsed: &dyn Any, arguments: &[&str]) -> Result<String, String> {
+    let lines: &Vec<String> = model(parsed);
+    let n = argument(arguments, "line")?;
+    let line = nth(lines, n, "line")?;
+    let value = |result: Result<u32, crate::error::LineError>| match result {
+        Ok(value) => value.to_string(),
+        Err(error) => error.locate(1, n - 1, line).to_string()
+    };
+    Ok(format!(
+        "{}\nPart 1: {}\nPart 2: {}",
+        line,
+        value(day1::part1_parse_line(line)),
+        value(day1::part2_parse_line(line))
+    ))
+}
+
+fn day2_game(parsed: &dyn Any, arguments: &[&str]) -> Result<String, String> {
+    let games: &Vec<day2::Game> = model(parsed);
+    let id: u32 = argument(arguments, "game id")?;
+    let game = games.iter()
+        .find(|game| game.id == id)
+        .ok_or_else(|| format!("No game {}", id))?;
+    let minimum = game.minimum_required();
+    Ok(format!(
+        "{}\nNeeds at least: {}\nPower: {}\nPossible with 12 red, 13 green, 14 blue: {}",
+        game,
+        minimum,
+        minimum.power(),
+        if game.is_possible() { "yes" } else { "no" }
+    ))
+}
+
+fn day3_gears(parsed: &dyn Any, arguments: &[&str]) -> Result<String, String> {
+    let schematic: &day3::Schematic = model(parsed);
+    no_arguments(arguments)?;
+    let lines: Vec<String> = schematic.stars()
+        .into_iter()
+        .map(|star| {
+            let numbers = schematic.numbers_for_gear(star);
+            let numbers_text: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+            let ratio = if numbers.len() == 2 {
+                format!(", gear ratio {}", Answer::from(numbers[0]) * Answer::from(numbers[1]))
+            } else {
+                String::new()
+            };
+            format!("Line {}, column {}: [{}]{}", star.y + 1, star.x + 1, numbers_text.join(", "), ratio)
+        })
+        .collect();
+    Ok(if lines.is_empty() { "No stars".to_owned() } else { lines.join("\n") })
+}
+
+fn day3_numbers(parsed: &dyn Any, arguments: &[&str]) -> Result<String, String> {
+    let schematic: &day3::Schematic = model(parsed);
+    no_arguments(arguments)?;
+    let lines: Vec<String> = schematic.numbers()
+        .iter()
+        .map(|number| format!(
+            "Line {}, column {}: {}{}",
+            number.start.y + 1,
+            number.start.x + 1,
+            number.value,
+            if schematic.is_part_number(number) { " (part number)" } else { "" }
+        ))
+        .collect();
+    Ok(if lines.is_empty() { "No numbers".to_owned() } else { lines.join("\n") })
+}
+
+fn day4_card(parsed: &dyn Any, arguments: &[&str]) -> Result<String, String> {
+    let cards: &Vec<day4::Card> = model(parsed);
+    let id: u32 = argument(arguments, "card id")?;
+    let i = cards.iter()
+        .position(|card| card.id == id)
+        .ok_or_else(|| format!("No card {}", id))?;
+    let card = &cards[i];
+    let matching: Vec<String> = card.matching_numbers()
+        .iter()
+        .flat_map(|(n, count)| std::iter::repeat_n(n.to_string(), count))
+        .collect();
+    let copies: Vec<String> = cards[i + 1..].iter()
+        .take(card.matching_cards())
+        .map(|card| card.id.to_string())
+        .collect();
+    Ok(format!(
+        "{}\nMatching numbers: {}\nPoints: {}\nWins copies of cards: {}",
+        card,
+        if matching.is_empty() { "none".to_owned() } else { matching.join(", ") },
+        card.points().map_or("too many to count".to_owned(), |points| points.to_string()),
+        if copies.is_empty() { "none".to_owned() } else { copies.join(", ") }
+    ))
+}
+
+fn day5_seed(parsed: &dyn Any, arguments: &[&str]) -> Result<String, String> {
+    let almanac: &day5::Almanac = model(parsed);
+    Ok(day5::seed_path(argument(arguments, "seed id")?, almanac))
+}
+
+fn day6_race(parsed: &dyn Any, arguments: &[&str]) -> Result<String, String> {
+    let sheet: &day6::Sheet = model(parsed);
+    let race = match arguments {
+        ["kerned"] => sheet.kerned_race,
+        _ => *nth(&sheet.races, argument(arguments, "race")?, "race")?
+    };
+    Ok(format!(
+        "Duration: {}\nRecord: {}\nWays to win: {}",
+        race.duration,
+        race.best_distance,
+        day6::win_possibilities(race)
+    ))
+}
+
+fn day7_bid(parsed: &dyn Any, arguments: &[&str]) -> Result<String, String> {
+    let bids: &day7::Bids = model(parsed);
+    let n = argument(arguments, "bid")?;
+    let part1 = nth(&bids.part1, n, "bid")?;
+    let part1_rank = bids.part1.iter()
+        .filter(|other| day7_part1::compare_hands(&other.cards, &part1.cards).is_lt())
+        .count() + 1;
+    let part2 = day7_part2::replace_jokers(&bids.part2);
+    let fixed_up = &part2[n - 1];
+    let part2_rank = part2.iter()
+        .filter(|other| day7_part2::compare_hands(&other.cards, &fixed_up.cards).is_lt())
+        .count() + 1;
+    Ok(format!(
+        "{}\nPart 1: {:?}, rank {} of {}\nPart 2: jokers make it {}, {:?}, rank {} of {}",
+        part1,
+        day7_part1::hand_type(&part1.cards),
+        part1_rank,
+        bids.part1.len(),
+        fixed_up,
+        day7_part2::hand_type(&fixed_up.cards),
+        part2_rank,
+        part2.len()
+    ))
+}
+
+#[cfg(test)]
+fn session(day: u32) -> Session {
+    let text = std::fs::read_to_string(format!("examples/{}/sample.txt", day)).unwrap();
+    Session::new(day, vec![1, 2], Input::Text(text)).unwrap()
+}
+
+#[cfg(test)]
+fn output(session: &mut Session, line: &str) -> Result<String, String> {
+    match session.execute(line)? {
+        Reply::Output(output) => Ok(output),
+        Reply::Quit => Err("quit".to_owned())
+    }
+}
+
+#[test]
+fn test_general_commands() {
+    let mut session = session(4);
+    assert_eq!(session.prompt(), "day 4> ");
+    assert_eq!(output(&mut session, "  "), Ok(String::new()));
+    assert_eq!(output(&mut session, "solve"), Ok("Part 1: 13\nPart 2: 30".to_owned()));
+    assert_eq!(output(&mut session, "solve 2"), Ok("Part 2: 30".to_owned()));
+    assert!(output(&mut session, "solve 3").is_err());
+    assert!(output(&mut session, "seed 79").is_err());
+    assert!(output(&mut session, "help").unwrap().contains("card <id>"));
+    assert_eq!(output(&mut session, "quit"), Err("quit".to_owned()));
+}
+
+#[test]
+fn test_day_commands() {
+    assert_eq!(
+        output(&mut session(5), "seed 79"),
+        Ok("seed 79 -> soil 81 -> fertilizer 81 -> water 81 -> light 74 -> temperature 78 \
+            -> humidity 78 -> location 82".to_owned())
+    );
+
+    let gears = output(&mut session(3), "gears").unwrap();
+    assert!(gears.contains("Line 2, column 4: [467, 35], gear ratio 16345"), "{}", gears);
+    assert!(gears.contains("Line 5, column 4: [617]\n"), "{}", gears);
+
+    let bid = output(&mut session(7), "bid 4").unwrap();
+    assert_eq!(bid, "KTJJT 220\nPart 1: TwoPair, rank 2 of 5\nPart 2: jokers make it KTTTT 220, FourOfAKind, rank 5 of 5");
+    assert!(output(&mut session(7), "bid 6").is_err());
+    assert!(output(&mut session(7), "bid 0").is_err());
+
+    for day in (1..=registry::available_days()).filter(|day| !registry::UNSOLVED.contains(day)) {
+        assert!(!day_commands(day).is_empty(), "day {} has no commands", day);
+    }
+}
@@ -0,0 +1,298 @@
+use crate::manifest::Status;
+use crate::solution::{Answer, Trace};
+use std::fmt::Write;
+use std::time::Duration;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!("Unknown format {:?}, expected text, json or csv", s))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    Solved { answer: Answer, status: Status },
+    Failed { error: String }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: u32,
+    pub outcome: Outcome,
+    pub duration: Duration,
+    pub recorded: bool,
+    // Only there when the answer was asked to be explained.
+    pub trace: Option<Trace>
+}
+
+impl PartResult {
+    pub fn answer(&self) -> Option<Answer> {
+        match self.outcome {
+            Outcome::Solved { answer, .. } => Some(answer),
+            Outcome::Failed { .. } => None
+        }
+    }
+
+    pub fn expected(&self) -> Option<Answer> {
+        match self.outcome {
+            Outcome::Solved { answer, status: Status::Pass } => Some(answer),
+            Outcome::Solved { status: Status::Fail { expected }, .. } => Some(expected),
+            _ => None
+        }
+    }
+
+    pub fn status(&self) -> &'static str {
+        match self.outcome {
+            Outcome::Solved { status: Status::Pass, .. } => "pass",
+            Outcome::Solved { status: Status::Fail { .. }, .. } => "fail",
+            Outcome::Solved { status: Status::Unknown, .. } => "unknown",
+            Outcome::Failed { .. } => "error"
+        }
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        match &self.outcome {
+            Outcome::Solved { .. } => None,
+            Outcome::Failed { error } => Some(error)
+        }
+    }
+
+    // A recorded answer becomes the expected one, so only unrecorded
+    // mismatches and errors count as failures.
+    pub fn is_failure(&self) -> bool {
+        match self.outcome {
+            Outcome::Solved { status: Status::Fail { .. }, .. } => !self.recorded,
+            Outcome::Solved { .. } => false,
+            Outcome::Failed { .. } => true
+        }
+    }
+}
+
+pub fn render(format: Format, results: &[PartResult]) -> String {
+    match format {
+        Format::Text => render_text(results),
+        Format::Json => render_json(results),
+        Format::Csv => render_csv(results)
+    }
+}
+
+fn render_text(results: &[PartResult]) -> String {
+    let mut result = String::new();
+
+    for part in results {
+        match &part.outcome {
+            Outcome::Solved { answer, status } => {
+                let recorded = if part.recorded { ", recorded" } else { "" };
+                writeln!(
+                    result,
+                    "Day {} part {}: {} ({}{}) [{:.2?}]",
+                    part.day,
+                    part.part,
+                    answer,
+                    status,
+                    recorded,
+                    part.duration
+                ).unwrap();
+
+                for step in part.trace.iter().flatten() {
+                    writeln!(result, "  {}", step).unwrap();
+                }
+            }
+            Outcome::Failed { error } => {
+                writeln!(
+                    result,
+                    "Day {} part {}: error: {}",
+                    part.day,
+                    part.part,
+                    error
+                ).unwrap();
+            }
+        }
+    }
+
+    result
+}
+
+fn render_json(results: &[PartResult]) -> String {
+    let mut result = String::from("[");
+
+    for (i, part) in results.iter().enumerate() {
+        if i > 0 {
+            result.push(',');
+        }
+
+        write!(
+            result,
+            "\n  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"expected\": {}, \
+             \"status\": \"{}\", \"time_ms\": {:.3}, \"recorded\": {}, \"error\": {}",
+            part.day,
+            part.part,
+            json_option(part.answer()),
+            json_option(part.expected()),
+            part.status(),
+            part.duration.as_secs_f64() * 1000.0,
+            part.recorded,
+            part.error().map(json_string).unwrap_or_else(|| "null".to_owned())
+        ).unwrap();
+
+        if let Some(trace) = &part.trace {
+            let steps: Vec<String> = trace.iter().map(|step| json_string(step)).collect();
+            write!(result, ", \"trace\": [{}]", steps.join(", ")).unwrap();
+        }
+
+        result.push('}');
+    }
+
+    result.push_str(if results.is_empty() { "]\n" } else { "\n]\n" });
+    result
+}
+
+fn render_csv(results: &[PartResult]) -> String {
+    let mut result = String::from("day,part,answer,expected,status,time_ms,recorded,error\n");
+
+    for part in results {
+        writeln!(
+            result,
+            "{},{},{},{},{},{:.3},{},{}",
+            part.day,
+            part.part,
+            csv_option(part.answer()),
+            csv_option(part.expected()),
+            part.status(),
+            part.duration.as_secs_f64() * 1000.0,
+            part.recorded,
+            part.error().map(csv_string).unwrap_or_default()
+        ).unwrap();
+    }
+
+    result
+}
+
+fn json_option(value: Option<Answer>) -> String {
+    value.map(|value| value.to_string())
+        .unwrap_or_else(|| "null".to_owned())
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                write!(result, "\\u{:04x}", c as u32).unwrap();
+            }
+            c => result.push(c)
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+fn csv_option(value: Option<Answer>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+fn csv_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+fn sample_results() -> Vec<PartResult> {
+    vec![
+        PartResult {
+            day: 5,
+            part: 1,
+            outcome: Outcome::Solved { answer: 35, status: Status::Pass },
+            duration: Duration::from_micros(1500),
+            recorded: false,
+            trace: None
+        },
+        PartResult {
+            day: 5,
+            part: 2,
+            outcome: Outcome::Solved { answer: 47, status: Status::Fail { expected: 46 } },
+            duration: Duration::from_millis(20),
+            recorded: false,
+            trace: None
+        },
+        PartResult {
+            day: 6,
+            part: 1,
+            outcome: Outcome::Failed { error: "line 1: expected \"Time:\"\n  ^".to_owned() },
+            duration: Duration::ZERO,
+            recorded: false,
+            trace: None
+        }
+    ]
+}
+
+#[test]
+fn test_render_json() {
+    assert_eq!(render(Format::Json, &[]), "[]\n");
+    assert_eq!(
+        render(Format::Json, &sample_results()),
+        "[\n  \
+         {\"day\": 5, \"part\": 1, \"answer\": 35, \"expected\": 35, \"status\": \"pass\", \
+         \"time_ms\": 1.500, \"recorded\": false, \"error\": null},\n  \
+         {\"day\": 5, \"part\": 2, \"answer\": 47, \"expected\": 46, \"status\": \"fail\", \
+         \"time_ms\": 20.000, \"recorded\": false, \"error\": null},\n  \
+         {\"day\": 6, \"part\": 1, \"answer\": null, \"expected\": null, \"status\": \"error\", \
+         \"time_ms\": 0.000, \"recorded\": false, \
+         \"error\": \"line 1: expected \\\"Time:\\\"\\n  ^\"}\n\
+         ]\n"
+    );
+}
+
+#[test]
+fn test_render_csv() {
+    assert_eq!(
+        render(Format::Csv, &sample_results()),
+        "day,part,answer,expected,status,time_ms,recorded,error\n\
+         5,1,35,35,pass,1.500,false,\n\
+         5,2,47,46,fail,20.000,false,\n\
+         6,1,,,error,0.000,false,\"line 1: expected \"\"Time:\"\"\n  ^\"\n"
+    );
+}
+
+#[test]
+fn test_is_failure() {
+    let results = sample_results();
+    assert!(!results[0].is_failure());
+    assert!(results[1].is_failure());
+    assert!(results[2].is_failure());
+    assert!(!PartResult { recorded: true, ..results[1].clone() }.is_failure());
+}
+
+#[test]
+fn test_render_trace() {
+    let mut results = sample_results();
+    results[0].trace = Some(vec!["seed 79 -> location 82".to_owned(), "seed \"14\"".to_owned()]);
+    results.truncate(1);
+    assert_eq!(
+        render(Format::Text, &results),
+        "Day 5 part 1: 35 (pass) [1.50ms]\n  seed 79 -> location 82\n  seed \"14\"\n"
+    );
+    assert!(render(Format::Json, &results)
+            .ends_with("\"error\": null, \"trace\": [\"seed 79 -> location 82\", \"seed \\\"14\\\"\"]}\n]\n"));
+}
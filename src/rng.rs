@@ -0,0 +1,84 @@
+use std::ops::RangeInclusive;
+
+// SplitMix64. The same seed always produces the same sequence, on every
+// platform, which is what generated inputs need to be reproducible.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // A number in 0..n, with a negligible bias for the small n used here.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0);
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end);
+
+        match (end - start).checked_add(1) {
+            Some(n) => start + self.below(n),
+            None => self.next_u64()
+        }
+    }
+
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+#[test]
+fn test_sequence() {
+    let mut rng = Rng::new(0);
+    assert_eq!(rng.next_u64(), 0xe220a8397b1dcdaf);
+    assert_eq!(rng.next_u64(), 0x6e789e6aa1b965f4);
+
+    let a: Vec<u64> = (0..10).map({ let mut rng = Rng::new(42); move |_| rng.next_u64() }).collect();
+    let b: Vec<u64> = (0..10).map({ let mut rng = Rng::new(42); move |_| rng.next_u64() }).collect();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_bounds() {
+    let mut rng = Rng::new(7);
+    let mut seen = [false; 6];
+
+    for _ in 0..1000 {
+        let n = rng.range(5..=10);
+        assert!((5..=10).contains(&n));
+        seen[n as usize - 5] = true;
+    }
+
+    assert_eq!(seen, [true; 6]);
+    assert_eq!(rng.range(3..=3), 3);
+    rng.range(0..=u64::MAX);
+
+    let mut items: Vec<u32> = (0..20).collect();
+    rng.shuffle(&mut items);
+    items.sort();
+    assert_eq!(items, (0..20).collect::<Vec<u32>>());
+}
@@ -0,0 +1,122 @@
+use crate::input::Input;
+use crate::manifest::{self, Manifest};
+use crate::pool;
+use crate::registry;
+use crate::report::{Outcome, PartResult};
+use crate::solution::{Answer, AnySolution, Trace};
+use std::time::Instant;
+
+// Solves every selected part on `jobs` threads, then checks the answers
+// against the manifest, recording them there if asked to. Results are in
+// day then part order whatever the number of jobs.
+pub fn run(
+    days: &[u32],
+    parts: &[u32],
+    input: &Input,
+    jobs: usize,
+    manifest: &mut Manifest,
+    record: bool,
+    explain: bool
+) -> Vec<PartResult> {
+    // Inputs are read up front, as stdin can only be read once.
+    let texts: Vec<Result<String, String>> = days.iter()
+        .map(|&day| input.read(day))
+        .collect();
+    let tasks: Vec<(u32, u32, &Result<String, String>)> = days.iter()
+        .zip(&texts)
+        .flat_map(|(&day, text)| {
+            parts.iter().map(move |&part| (day, part, text))
+        })
+        .collect();
+    let answers = pool::map(jobs, &tasks, |&(day, part, text)| {
+        let start = Instant::now();
+        let answer = text.as_ref()
+            .map_err(|error| error.clone())
+            .and_then(|text| solve(registry::find(day).unwrap(), text, part));
+        let duration = start.elapsed();
+        // Explaining isn't part of the timing.
+        let trace = match (&answer, text) {
+            (Ok(_), Ok(text)) if explain => Some(explain_part(registry::find(day).unwrap(), text, part)),
+            _ => None
+        };
+        (answer, duration, trace)
+    });
+    let mut results = Vec::new();
+
+    for ((day, part, text), (answer, duration, trace)) in tasks.into_iter().zip(answers) {
+        let (answer, trace) = match trace {
+            Some(Ok(trace)) => (answer, Some(trace)),
+            Some(Err(error)) => (Err(error), None),
+            None => (answer, None)
+        };
+        // Only answers make it into the manifest, never failures.
+        let (outcome, recorded) = match (text, answer) {
+            (Ok(text), Ok(answer)) => {
+                let hash = manifest::input_hash(text);
+                let status = manifest.check(day, part, hash, answer);
+
+                if record {
+                    manifest.record(day, part, hash, answer);
+                }
+
+                (Outcome::Solved { answer, status }, record)
+            }
+            (_, Err(error)) => (Outcome::Failed { error }, false),
+            (Err(_), Ok(_)) => unreachable!()
+        };
+        results.push(PartResult {
+            day,
+            part,
+            outcome,
+            duration,
+            recorded,
+            trace
+        });
+    }
+
+    results
+}
+
+// Solver panics are reported like any other failure instead of aborting the
+// whole run.
+pub fn solve(solution: &dyn AnySolution, text: &str, part: u32) -> Result<Answer, String> {
+    let solver = std::panic::AssertUnwindSafe(|| solution.solve(text, part));
+
+    match std::panic::catch_unwind(solver) {
+        Ok(result) => result.map_err(|error| error.to_string()),
+        Err(payload) => Err(panic_message(payload.as_ref()))
+    }
+}
+
+// Explains an answer, with the same handling of panics as solve.
+pub fn explain_part(solution: &dyn AnySolution, text: &str, part: u32) -> Result<Trace, String> {
+    let explainer = std::panic::AssertUnwindSafe(|| {
+        let parsed = solution.parse_any(text)?;
+        solution.explain_any(parsed.as_ref(), part)
+    });
+
+    match std::panic::catch_unwind(explainer) {
+        Ok(result) => result.map_err(|error| error.to_string()),
+        Err(payload) => Err(panic_message(payload.as_ref()))
+    }
+}
+
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown error".to_owned()
+    }
+}
+
+#[test]
+fn test_recorded() {
+    let mut manifest = Manifest::default();
+    let solved = run(&[6], &[1], &Input::Text("Time: 7\nDistance: 9".to_owned()), 1, &mut manifest, true, false);
+    assert!(solved[0].recorded);
+    let failed = run(&[6], &[1], &Input::Text("Time: 7".to_owned()), 1, &mut manifest, true, false);
+    assert!(matches!(failed[0].outcome, Outcome::Failed { .. }));
+    assert!(!failed[0].recorded);
+}
@@ -0,0 +1,216 @@
+use crate::fixture;
+use crate::input;
+use std::path::{Path, PathBuf};
+
+const MODULE_TEMPLATE: &str = "use crate::error::{LineError, ParseError, SolveError};
+use crate::parser;
+use crate::solution::{Answer, Solution};
+
+const DAY: u32 = __DAY__;
+
+pub struct Day__DAY__;
+
+impl Solution for Day__DAY__ {
+    type Parsed = Vec<String>;
+
+    fn day(&self) -> u32 {
+        DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        parser::lines(input.lines(), DAY, parse_line)
+    }
+
+    fn part1(&self, _lines: &Vec<String>) -> Result<Answer, SolveError> {
+        Err(SolveError::Unsolvable { day: DAY, part: 1, reason: \"not solved yet\" })
+    }
+
+    fn part2(&self, _lines: &Vec<String>) -> Result<Answer, SolveError> {
+        Err(SolveError::Unsolvable { day: DAY, part: 2, reason: \"not solved yet\" })
+    }
+}
+
+fn parse_line(line: &str) -> Result<String, LineError> {
+    Ok(line.to_owned())
+}
+";
+
+pub fn module_source(day: u32) -> String {
+    MODULE_TEMPLATE.replace("__DAY__", &day.to_string())
+}
+
+// Adds `pub mod day<N>;` after the last day module of lib.rs.
+pub fn register_module(lib: &str, day: u32) -> Result<String, String> {
+    let declaration = format!("pub mod day{};", day);
+    let mut lines: Vec<&str> = lib.lines().collect();
+
+    if lines.contains(&declaration.as_str()) {
+        return Err(format!("lib.rs already declares day{}", day));
+    }
+
+    let last_day = lines.iter()
+        .rposition(|line| line.starts_with("pub mod day"))
+        .ok_or("lib.rs has no day modules")?;
+    lines.insert(last_day + 1, &declaration);
+    Ok(lines.join("\n") + "\n")
+}
+
+// Adds the day to the imports and to the end of SOLUTIONS in registry.rs.
+pub fn register_solution(registry: &str, day: u32) -> Result<String, String> {
+    let module = format!("day{}", day);
+    let imports_start = registry.find("use crate::{")
+        .ok_or("registry.rs has no day imports")?
+        + "use crate::{".len();
+    let imports_end = imports_start + registry[imports_start..].find("};")
+        .ok_or("registry.rs has no day imports")?;
+    let mut imports: Vec<&str> = registry[imports_start..imports_end]
+        .split(',')
+        .map(|import| import.trim())
+        .collect();
+
+    if imports.contains(&module.as_str()) {
+        return Err(format!("registry.rs already imports {}", module));
+    }
+
+    imports.push(&module);
+    let solutions_end = registry.find("\n];")
+        .ok_or("registry.rs has no SOLUTIONS list")?;
+
+    if solutions_end < imports_end {
+        return Err("registry.rs has no SOLUTIONS list".to_owned());
+    }
+
+    Ok(format!(
+        "{}{}{},\n    &{}::Day{}{}",
+        &registry[..imports_start],
+        imports.join(", "),
+        &registry[imports_end..solutions_end],
+        module,
+        day,
+        &registry[solutions_end..]
+    ))
+}
+
+// Adds the day to UNSOLVED in registry.rs, until its parts are solved.
+pub fn mark_unsolved(registry: &str, day: u32) -> Result<String, String> {
+    let start = registry.find(UNSOLVED_START)
+        .ok_or("registry.rs has no UNSOLVED list")?
+        + UNSOLVED_START.len();
+    let end = start + registry[start..].find("];")
+        .ok_or("registry.rs has no UNSOLVED list")?;
+    let day = day.to_string();
+    let mut days: Vec<&str> = registry[start..end]
+        .split(',')
+        .map(|day| day.trim())
+        .filter(|day| !day.is_empty())
+        .collect();
+
+    if days.contains(&day.as_str()) {
+        return Err(format!("registry.rs already lists day {} as unsolved", day));
+    }
+
+    days.push(&day);
+    Ok(format!("{}{}{}", &registry[..start], days.join(", "), &registry[end..]))
+}
+
+const UNSOLVED_START: &str = "pub const UNSOLVED: &[u32] = &[";
+
+// Creates everything a new day needs under the repository at `root`, and
+// returns the paths that were created or changed.
+pub fn create(root: &Path, day: u32) -> Result<Vec<PathBuf>, String> {
+    let module_path = root.join("src").join(format!("day{}.rs", day));
+    let input_path = root.join(input::DEFAULT_DIRECTORY).join(format!("{}.txt", day));
+    let example_directory = root.join(fixture::DEFAULT_DIRECTORY).join(day.to_string());
+    let example_path = example_directory.join("sample.txt");
+    let answers_path = example_directory.join("sample.answers");
+    let lib_path = root.join("src").join("lib.rs");
+    let registry_path = root.join("src").join("registry.rs");
+
+    for path in [&module_path, &input_path, &example_path, &answers_path] {
+        if path.exists() {
+            return Err(format!("{} already exists", path.display()));
+        }
+    }
+
+    let read = |path: &Path| std::fs::read_to_string(path)
+        .map_err(|error| format!("cannot read {}: {}", path.display(), error));
+    let lib = register_module(&read(&lib_path)?, day)?;
+    let registry = mark_unsolved(&register_solution(&read(&registry_path)?, day)?, day)?;
+    let files = [
+        (&module_path, module_source(day)),
+        (&input_path, String::new()),
+        (&example_path, String::new()),
+        (&answers_path, format!("{}\n", fixture::ANSWERS_HEADER)),
+        (&lib_path, lib),
+        (&registry_path, registry)
+    ];
+
+    std::fs::create_dir_all(&example_directory)
+        .map_err(|error| format!("cannot create {}: {}", example_directory.display(), error))?;
+
+    for (path, contents) in &files {
+        std::fs::write(path, contents)
+            .map_err(|error| format!("cannot write {}: {}", path.display(), error))?;
+    }
+
+    Ok(files.into_iter().map(|(path, _)| path.clone()).collect())
+}
+
+#[test]
+fn test_register_module() {
+    let lib = "pub mod bench;\npub mod day1;\npub mod day7_part2;\npub mod error;\n";
+    assert_eq!(
+        register_module(lib, 8),
+        Ok("pub mod bench;\npub mod day1;\npub mod day7_part2;\npub mod day8;\npub mod error;\n"
+           .to_owned())
+    );
+    assert!(register_module(lib, 1).is_err());
+    assert!(register_module("pub mod bench;\n", 1).is_err());
+}
+
+#[test]
+fn test_register_solution() {
+    let registry = "use crate::solution::AnySolution;
+use crate::{day1, day2};
+
+pub const SOLUTIONS: &[&dyn AnySolution] = &[
+    &day1::Day1,
+    &day2::Day2
+];
+";
+    assert_eq!(
+        register_solution(registry, 3),
+        Ok("use crate::solution::AnySolution;
+use crate::{day1, day2, day3};
+
+pub const SOLUTIONS: &[&dyn AnySolution] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3
+];
+".to_owned())
+    );
+    assert!(register_solution(registry, 2).is_err());
+}
+
+#[test]
+fn test_mark_unsolved() {
+    let registry = "pub const SOLUTIONS: &[&dyn AnySolution] = &[\n    &day1::Day1\n];\n\n\
+                    pub const UNSOLVED: &[u32] = &[];\n";
+    let marked = mark_unsolved(registry, 8).unwrap();
+    assert!(marked.ends_with("pub const UNSOLVED: &[u32] = &[8];\n"), "{}", marked);
+    assert!(mark_unsolved(&marked, 9).unwrap().ends_with("&[8, 9];\n"));
+    assert!(mark_unsolved(&marked, 8).is_err());
+    assert!(mark_unsolved("pub const SOLUTIONS: &[u32] = &[];\n", 8).is_err());
+}
+
+#[test]
+fn test_registers_current_tree() {
+    let lib = include_str!("lib.rs");
+    let registry = include_str!("registry.rs");
+    let next = crate::registry::available_days() + 1;
+    assert!(register_module(lib, next).unwrap().contains(&format!("pub mod day{};", next)));
+    assert!(register_solution(registry, next).unwrap()
+            .contains(&format!("&day{}::Day{}\n];", next, next)));
+    assert!(mark_unsolved(registry, next).is_ok());
+}
@@ -0,0 +1,84 @@
+use crate::error::{ParseError, SolveError};
+use std::any::Any;
+
+pub type Answer = u64;
+
+// Adds up the values an answer is made of, failing with `what` overflowed when
+// the total doesn't fit. None stands for a value that overflowed on its own.
+pub fn checked_sum(
+    day: u32,
+    part: u32,
+    what: &'static str,
+    values: impl IntoIterator<Item = Option<Answer>>
+) -> Result<Answer, SolveError> {
+    let mut total: Answer = 0;
+
+    for value in values {
+        total = value.and_then(|value| total.checked_add(value))
+            .ok_or(SolveError::Overflow { day, part, what })?;
+    }
+
+    Ok(total)
+}
+
+// How an answer was reached, one step per line, e.g. the value found on each
+// input line.
+pub type Trace = Vec<String>;
+
+// Parts can still reject input that only one of them can't make sense of,
+// like a day 1 line whose digits are all spelled out, or find that the answer
+// is too large for an Answer.
+pub trait Solution {
+    type Parsed: 'static;
+
+    fn day(&self) -> u32;
+    fn parse(&self, input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part1(&self, parsed: &Self::Parsed) -> Result<Answer, SolveError>;
+    fn part2(&self, parsed: &Self::Parsed) -> Result<Answer, SolveError>;
+
+    fn explain(&self, _parsed: &Self::Parsed, _part: u32) -> Result<Trace, SolveError> {
+        Ok(Vec::new())
+    }
+}
+
+// Object-safe view of a Solution, so that days with different parsed models
+// can live side by side in the registry.
+pub trait AnySolution: Sync {
+    fn day(&self) -> u32;
+    fn parse_any(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+    fn solve_any(&self, parsed: &dyn Any, part: u32) -> Result<Answer, SolveError>;
+    fn explain_any(&self, parsed: &dyn Any, part: u32) -> Result<Trace, SolveError>;
+
+    fn solve(&self, input: &str, part: u32) -> Result<Answer, SolveError> {
+        let parsed = self.parse_any(input)?;
+        self.solve_any(parsed.as_ref(), part)
+    }
+}
+
+impl<S: Solution + Sync> AnySolution for S {
+    fn day(&self) -> u32 {
+        Solution::day(self)
+    }
+
+    fn parse_any(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(self.parse(input)?))
+    }
+
+    fn solve_any(&self, parsed: &dyn Any, part: u32) -> Result<Answer, SolveError> {
+        let parsed = parsed.downcast_ref::<S::Parsed>()
+            .expect("Parsed model belongs to another day");
+
+        match part {
+            1 => self.part1(parsed),
+            2 => self.part2(parsed),
+            _ => panic!("Unknown part {}", part)
+        }
+    }
+
+    fn explain_any(&self, parsed: &dyn Any, part: u32) -> Result<Trace, SolveError> {
+        let parsed = parsed.downcast_ref::<S::Parsed>()
+            .expect("Parsed model belongs to another day");
+        assert!(part == 1 || part == 2, "Unknown part {}", part);
+        Solution::explain(self, parsed, part)
+    }
+}
@@ -0,0 +1,298 @@
+use crate::fetch;
+use crate::http;
+use crate::solution::Answer;
+use std::path::Path;
+use std::time::Duration;
+
+pub const DEFAULT_HISTORY_PATH: &str = "problem_inputs/submissions.txt";
+
+const HEADER: &str = "# day part answer verdict";
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited { wait: Duration },
+    // The part was already solved, or part 1 isn't solved yet.
+    WrongLevel
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "too-high"),
+            Self::TooLow => write!(f, "too-low"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::RateLimited { wait } => write!(f, "rate-limited-{}s", wait.as_secs()),
+            Self::WrongLevel => write!(f, "wrong-level")
+        }
+    }
+}
+
+impl std::str::FromStr for Verdict {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Self::Correct),
+            "too-high" => Ok(Self::TooHigh),
+            "too-low" => Ok(Self::TooLow),
+            "wrong" => Ok(Self::Wrong),
+            "wrong-level" => Ok(Self::WrongLevel),
+            s => {
+                let seconds = s.strip_prefix("rate-limited-")
+                    .and_then(|s| s.strip_suffix('s'))
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .ok_or(())?;
+                Ok(Self::RateLimited { wait: Duration::from_secs(seconds) })
+            }
+        }
+    }
+}
+
+// Reads the verdict out of the HTML page returned after posting an answer.
+pub fn parse_verdict(page: &str) -> Result<Verdict, String> {
+    if page.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if page.contains("That's not the right answer") {
+        if page.contains("your answer is too high") {
+            Ok(Verdict::TooHigh)
+        } else if page.contains("your answer is too low") {
+            Ok(Verdict::TooLow)
+        } else {
+            Ok(Verdict::Wrong)
+        }
+    } else if page.contains("You gave an answer too recently") {
+        Ok(Verdict::RateLimited { wait: parse_wait(page).unwrap_or(Duration::ZERO) })
+    } else if page.contains("You don't seem to be solving the right level") {
+        Ok(Verdict::WrongLevel)
+    } else {
+        Err("Unrecognized response to the submission".to_owned())
+    }
+}
+
+// Parses "you have 1m 5s left to wait".
+fn parse_wait(page: &str) -> Option<Duration> {
+    let end = page.find(" left to wait")?;
+    let start = page[..end].rfind("have ")? + "have ".len();
+    let mut seconds = 0;
+
+    for amount in page[start..end].split_ascii_whitespace() {
+        seconds += if let Some(minutes) = amount.strip_suffix('m') {
+            minutes.parse::<u64>().ok()? * 60
+        } else {
+            amount.strip_suffix('s')?.parse::<u64>().ok()?
+        };
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Attempt {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Answer,
+    pub verdict: Verdict
+}
+
+// Every answer that was submitted, in order, with what the server made of it.
+#[derive(Debug, Default, PartialEq)]
+pub struct History {
+    attempts: Vec<Attempt>
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text)
+                .map_err(|error| format!("{}: {}", path.display(), error)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                Ok(Self::default())
+            }
+            Err(error) => {
+                Err(format!("cannot read {}: {}", path.display(), error))
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_string())
+            .map_err(|error| format!("cannot write {}: {}", path.display(), error))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut attempts = Vec::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_ascii_whitespace().collect();
+            let invalid = || format!("invalid entry on line {}: {:?}", i + 1, line);
+
+            if fields.len() != 4 {
+                return Err(invalid());
+            }
+
+            attempts.push(Attempt {
+                day: fields[0].parse().map_err(|_| invalid())?,
+                part: fields[1].parse().map_err(|_| invalid())?,
+                answer: fields[2].parse().map_err(|_| invalid())?,
+                verdict: fields[3].parse().map_err(|_| invalid())?
+            });
+        }
+
+        Ok(Self { attempts })
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    // Explains why submitting `answer` would be pointless, if it would be.
+    pub fn check(&self, day: u32, part: u32, answer: Answer) -> Result<(), String> {
+        let mut low = None;
+        let mut high = None;
+
+        for attempt in &self.attempts {
+            if (attempt.day, attempt.part) != (day, part) {
+                continue;
+            }
+
+            match attempt.verdict {
+                Verdict::Correct => {
+                    return Err(format!("Already solved with {}", attempt.answer));
+                }
+                Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong if attempt.answer == answer => {
+                    return Err(format!("{} was already rejected ({})", answer, attempt.verdict));
+                }
+                Verdict::TooHigh => {
+                    high = Some(high.map_or(attempt.answer, |high: Answer| high.min(attempt.answer)));
+                }
+                Verdict::TooLow => {
+                    low = low.max(Some(attempt.answer));
+                }
+                _ => {}
+            }
+        }
+
+        match (low, high) {
+            (Some(low), _) if answer <= low => {
+                Err(format!("{} is too low, as {} already was", answer, low))
+            }
+            (_, Some(high)) if answer >= high => {
+                Err(format!("{} is too high, as {} already was", answer, high))
+            }
+            _ => Ok(())
+        }
+    }
+}
+
+impl std::fmt::Display for History {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+
+        for attempt in &self.attempts {
+            writeln!(f, "{} {} {} {}", attempt.day, attempt.part, attempt.answer, attempt.verdict)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn submit(
+    base_url: &str,
+    day: u32,
+    part: u32,
+    answer: Answer,
+    session: &str
+) -> Result<Verdict, String> {
+    let url = format!("{}/day/{}/answer", base_url.trim_end_matches('/'), day);
+    let headers = fetch::headers(session);
+    let mut headers: Vec<(&str, &str)> = headers.iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect();
+    headers.push(("Content-Type", "application/x-www-form-urlencoded"));
+    let body = format!("level={}&answer={}", part, answer);
+    let response = http::send("POST", &url, &headers, Some(&body))?;
+
+    if response.status != 200 {
+        return Err(format!(
+            "POST {} failed with status {}: {}",
+            url,
+            response.status,
+            response.body.trim()
+        ));
+    }
+
+    parse_verdict(&response.body)
+}
+
+#[test]
+fn test_parse_verdict() {
+    let data = [
+        ("<article><p>That's the right answer! You are one gold star closer.</p></article>",
+         Verdict::Correct),
+        ("<p>That's not the right answer; your answer is too high.  If you're stuck...</p>",
+         Verdict::TooHigh),
+        ("<p>That's not the right answer; your answer is too low.</p>", Verdict::TooLow),
+        ("<p>That's not the right answer.  If you're stuck...</p>", Verdict::Wrong),
+        ("<p>You gave an answer too recently; you have to wait after submitting an answer \
+          before trying again.  You have 37s left to wait.</p>",
+         Verdict::RateLimited { wait: Duration::from_secs(37) }),
+        ("<p>You gave an answer too recently; you have 4m 2s left to wait.</p>",
+         Verdict::RateLimited { wait: Duration::from_secs(242) }),
+        ("<p>You don't seem to be solving the right level.  Did you already complete it?</p>",
+         Verdict::WrongLevel)
+    ];
+
+    for (page, verdict) in data {
+        assert_eq!(parse_verdict(page), Ok(verdict), "{:?}", page);
+    }
+
+    assert!(parse_verdict("<html>Puzzle inputs differ by user.</html>").is_err());
+}
+
+#[test]
+fn test_history_check() {
+    let history = History::parse(
+        "# day part answer verdict\n\
+         5 2 100 too-high\n\
+         5 2 10 too-low\n\
+         5 2 50 rate-limited-30s\n\
+         5 2 60 wrong\n\
+         5 2 80 too-high\n\
+         6 1 7 correct\n"
+    ).unwrap();
+    assert_eq!(history.check(5, 2, 50), Ok(()));
+    assert_eq!(history.check(5, 2, 79), Ok(()));
+    assert!(history.check(5, 2, 60).is_err());
+    assert!(history.check(5, 2, 10).is_err());
+    assert!(history.check(5, 2, 9).is_err());
+    assert!(history.check(5, 2, 80).is_err());
+    assert!(history.check(5, 2, 90).is_err());
+    assert!(history.check(6, 1, 8).is_err());
+    assert_eq!(history.check(6, 2, 7), Ok(()));
+    assert_eq!(History::parse(&history.to_string()), Ok(history));
+    assert!(History::parse("5 2 100 maybe").is_err());
+}
+
+#[test]
+fn test_submit() {
+    let (url, server) = http::serve(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 27\r\n\r\nThat's the right answer! :)".to_owned()
+    ]);
+    assert_eq!(submit(&url, 6, 2, 71503, "abc"), Ok(Verdict::Correct));
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("POST /day/6/answer HTTP/1.1\r\n"));
+    assert!(requests[0].contains("\r\nCookie: session=abc\r\n"));
+    assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=71503"));
+}
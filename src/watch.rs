@@ -0,0 +1,218 @@
+use crate::input::Input;
+use crate::solution::Answer;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Modification times of the files a day depends on. Files are listed again on
+// every poll, so that new modules for the day are picked up too.
+pub type Stamps = BTreeMap<PathBuf, SystemTime>;
+
+pub fn stamps(source_directory: &Path, day: u32, input: &Input) -> Stamps {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(source_directory)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_name().to_str().is_some_and(|name| is_day_source(name, day)))
+        .map(|entry| entry.path())
+        .collect();
+    paths.extend(input.path(day));
+    paths.into_iter()
+        .filter_map(|path| {
+            let modified = std::fs::metadata(&path).and_then(|metadata| metadata.modified());
+            modified.ok().map(|modified| (path, modified))
+        })
+        .collect()
+}
+
+// dayN.rs and dayN_*.rs, but not day1N.rs.
+fn is_day_source(name: &str, day: u32) -> bool {
+    name.strip_prefix(&format!("day{}", day))
+        .and_then(|rest| rest.strip_suffix(".rs"))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('_'))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartSummary {
+    pub part: u32,
+    pub outcome: Result<(Answer, String), String>
+}
+
+impl PartSummary {
+    // What changed since the previous run of the same part.
+    pub fn change(&self, previous: Option<&PartSummary>) -> String {
+        match (previous.map(|previous| &previous.outcome), &self.outcome) {
+            (Some(Ok((before, _))), Ok((after, _))) if before == after => "unchanged".to_owned(),
+            (Some(Ok((before, _))), _) => format!("was {}", before),
+            (Some(Err(_)), Ok(_)) => "was an error".to_owned(),
+            (Some(Err(_)), Err(_)) => "still an error".to_owned(),
+            (None, _) => "first run".to_owned()
+        }
+    }
+}
+
+fn cargo() -> Command {
+    Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+}
+
+// Runs the day's unit tests and the examples of every day, with their output
+// going straight to the terminal.
+pub fn run_tests(day: u32) -> Result<bool, String> {
+    let run = |command: &mut Command| command.status()
+        .map(|status| status.success())
+        .map_err(|error| format!("cannot run cargo: {}", error));
+    let unit_tests = run(cargo()
+        .args(["test", "--quiet", "--lib", "--"])
+        .args([format!("day{}::", day), format!("day{}_", day)]))?;
+    let examples = run(cargo().args(["test", "--quiet", "--test", "examples"]))?;
+    Ok(unit_tests && examples)
+}
+
+// Solves the day through a freshly built binary, as the running one can't
+// pick up source changes.
+pub fn run_day(day: u32, parts: &[u32], input: &Input) -> Result<Vec<PartSummary>, String> {
+    let mut command = cargo();
+    command.args(["run", "--quiet", "--", "run", &day.to_string(), "--format", "csv"]);
+
+    if let [part] = parts {
+        command.args(["--part", &part.to_string()]);
+    }
+
+    match input {
+        Input::Directory(directory) => {
+            command.arg("--input-dir").arg(directory);
+        }
+        Input::File(path) => {
+            command.arg("--input").arg(path);
+        }
+        Input::Stdin | Input::Text(_) => {
+            return Err("only input files can be watched".to_owned());
+        }
+    }
+
+    let output = command.stderr(Stdio::inherit())
+        .output()
+        .map_err(|error| format!("cannot run cargo: {}", error))?;
+    let rows = parse_csv(&String::from_utf8_lossy(&output.stdout));
+
+    if rows.len() < 2 {
+        return Err("the day didn't run".to_owned());
+    }
+
+    rows[1..].iter()
+        .map(|row| parse_summary(row).ok_or_else(|| format!("unexpected output {:?}", row)))
+        .collect()
+}
+
+// Reads a row of `run --format csv`.
+fn parse_summary(row: &[String]) -> Option<PartSummary> {
+    let [_, part, answer, _, status, _, _, error] = row else {
+        return None;
+    };
+    let outcome = if status == "error" {
+        Err(error.clone())
+    } else {
+        Ok((answer.parse().ok()?, status.clone()))
+    };
+    Some(PartSummary { part: part.parse().ok()?, outcome })
+}
+
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (c, _) => field.push(c)
+        }
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows
+}
+
+#[test]
+fn test_is_day_source() {
+    assert!(is_day_source("day7.rs", 7));
+    assert!(is_day_source("day7_part2.rs", 7));
+    assert!(!is_day_source("day17.rs", 7));
+    assert!(!is_day_source("day1.rs", 7));
+    assert!(!is_day_source("day70.rs", 7));
+    assert!(!is_day_source("day7.rs.orig", 7));
+}
+
+#[test]
+fn test_stamps() {
+    let directory = std::env::temp_dir()
+        .join(format!("adventofcode2023-watch-{}", std::process::id()));
+    std::fs::remove_dir_all(&directory).ok();
+    std::fs::create_dir_all(directory.join("src")).unwrap();
+    std::fs::create_dir_all(directory.join("inputs")).unwrap();
+
+    for name in ["src/day2.rs", "src/day2_extra.rs", "src/day12.rs", "inputs/2.txt"] {
+        std::fs::write(directory.join(name), "").unwrap();
+    }
+
+    let input = Input::Directory(directory.join("inputs"));
+    let stamps = stamps(&directory.join("src"), 2, &input);
+    assert_eq!(
+        stamps.keys().cloned().collect::<Vec<PathBuf>>(),
+        vec![
+            directory.join("inputs/2.txt"),
+            directory.join("src/day2.rs"),
+            directory.join("src/day2_extra.rs")
+        ]
+    );
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_parse_run_output() {
+    let rows = parse_csv(
+        "day,part,answer,expected,status,time_ms,recorded,error\n\
+         6,1,288,288,pass,0.010,false,\n\
+         6,2,,,error,0.000,false,\"line 1: expected \"\"Time:\"\"\n  ^\"\n"
+    );
+    assert_eq!(rows.len(), 3);
+    assert_eq!(
+        parse_summary(&rows[1]),
+        Some(PartSummary { part: 1, outcome: Ok((288, "pass".to_owned())) })
+    );
+    assert_eq!(
+        parse_summary(&rows[2]),
+        Some(PartSummary { part: 2, outcome: Err("line 1: expected \"Time:\"\n  ^".to_owned()) })
+    );
+    assert_eq!(parse_summary(&rows[0]), None);
+}
+
+#[test]
+fn test_change() {
+    let solved = |answer| PartSummary { part: 1, outcome: Ok((answer, "unknown".to_owned())) };
+    let failed = PartSummary { part: 1, outcome: Err("oops".to_owned()) };
+    assert_eq!(solved(5).change(None), "first run");
+    assert_eq!(solved(5).change(Some(&solved(5))), "unchanged");
+    assert_eq!(solved(5).change(Some(&solved(4))), "was 4");
+    assert_eq!(failed.change(Some(&solved(4))), "was 4");
+    assert_eq!(solved(5).change(Some(&failed)), "was an error");
+    assert_eq!(failed.change(Some(&failed)), "still an error");
+}
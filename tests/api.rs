@@ -0,0 +1,58 @@
+use adventofcode2023::solution::Solution;
+use adventofcode2023::{day2, day5, day7_part1, day7_part2, registry};
+
+#[test]
+fn test_every_day_is_registered() {
+    for day in 1..=registry::available_days() {
+        let solution = registry::find(day).unwrap();
+        assert_eq!(solution.day(), day);
+    }
+
+    assert!(registry::find(registry::available_days() + 1).is_none());
+}
+
+#[test]
+fn test_day2_model() {
+    let games = day2::Day2
+        .parse("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")
+        .unwrap();
+    assert!(games[0].is_possible());
+    assert_eq!(
+        games[0].minimum_required(),
+        day2::Cubes { red: 4, green: 2, blue: 6 }
+    );
+}
+
+#[test]
+fn test_day5_seed_location() {
+    let lines = [
+        "seeds: 79 14",
+        "",
+        "seed-to-soil map:",
+        "50 98 2",
+        "52 50 48",
+        "",
+        "soil-to-location map:",
+        "0 15 37"
+    ];
+    let almanac = day5::parse_almanac(&lines).unwrap();
+    assert_eq!(almanac.seeds, vec![79, 14]);
+    assert_eq!(almanac.maps.len(), 2);
+    assert_eq!(almanac.maps[0].from, day5::Resource::Seed);
+    assert_eq!(day5::seed_location(79, &almanac), 81);
+    assert_eq!(day5::seed_location(14, &almanac), 14);
+}
+
+#[test]
+fn test_day7_hand_ranking() {
+    let bids = day7_part1::parse(&["32T3K 765", "KK677 28"]).unwrap();
+    assert_eq!(day7_part1::hand_type(&bids[0].cards), day7_part1::HandType::OnePair);
+    assert_eq!(
+        day7_part1::compare_hands(&bids[0].cards, &bids[1].cards),
+        std::cmp::Ordering::Less
+    );
+
+    let bids = day7_part2::parse(&["KTJJT 220"]).unwrap();
+    let cards = day7_part2::replace_jokers_in_hand(&bids[0].cards);
+    assert_eq!(day7_part2::hand_type(&cards), day7_part2::HandType::FourOfAKind);
+}
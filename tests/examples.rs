@@ -0,0 +1,56 @@
+use adventofcode2023::{fixture, registry};
+use std::path::Path;
+
+// Runs every fixture under examples/ through its day's solver. Adding an
+// example only takes a new <name>.txt and <name>.answers pair.
+#[test]
+fn test_examples() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join(fixture::DEFAULT_DIRECTORY);
+    let fixtures = fixture::load_all(&directory).unwrap();
+    let mut failures = Vec::new();
+
+    for fixture in &fixtures {
+        let Some(solution) = registry::find(fixture.day) else {
+            failures.push(format!("{}/{}: day {} is not available", fixture.day, fixture.name, fixture.day));
+            continue;
+        };
+
+        for &(part, expected) in &fixture.answers {
+            match solution.solve(&fixture.input, part) {
+                Ok(answer) if answer == expected => {}
+                Ok(answer) => failures.push(format!(
+                    "{}/{} part {}: got {}, expected {}",
+                    fixture.day,
+                    fixture.name,
+                    part,
+                    answer,
+                    expected
+                )),
+                Err(error) => failures.push(format!(
+                    "{}/{} part {}: {}",
+                    fixture.day,
+                    fixture.name,
+                    part,
+                    error
+                ))
+            }
+        }
+    }
+
+    for day in 1..=registry::available_days() {
+        if registry::UNSOLVED.contains(&day) {
+            continue;
+        }
+
+        for part in [1, 2] {
+            let covered = fixtures.iter()
+                .any(|fixture| fixture.day == day && fixture.answers.iter().any(|&(p, _)| p == part));
+
+            if !covered {
+                failures.push(format!("day {} part {} has no example", day, part));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
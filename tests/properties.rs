@@ -0,0 +1,159 @@
+use adventofcode2023::rng::Rng;
+use adventofcode2023::solution::Solution;
+use adventofcode2023::{day1, day2, day3, day4, day5, day6, day7, day7_part1, day7_part2, generate};
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display};
+
+const SEEDS: std::ops::Range<u64> = 0..100;
+
+// Checks the property on the parsed inputs generated for every seed, and
+// reports the input it fails on.
+fn for_all<S: Solution>(solution: &S, size: usize, mut property: impl FnMut(&S::Parsed) -> Result<(), String>) {
+    for seed in SEEDS {
+        let input = generate::generate(solution.day(), seed, size).unwrap();
+        let parsed = solution.parse(&input).unwrap();
+
+        if let Err(message) = property(&parsed) {
+            panic!("day {}, seed {} size {}: {}\n{}", solution.day(), seed, size, message, input);
+        }
+    }
+}
+
+fn check(condition: bool, message: impl FnOnce() -> String) -> Result<(), String> {
+    if condition {
+        Ok(())
+    } else {
+        Err(message())
+    }
+}
+
+fn lines<T: Display>(items: &[T]) -> String {
+    items.iter().map(|item| format!("{}\n", item)).collect()
+}
+
+// Printing a parsed input and parsing it again gives the same model, and
+// printing that gives the same text.
+fn round_trips<S: Solution>(solution: &S, print: impl Fn(&S::Parsed) -> String)
+where
+    S::Parsed: Debug + PartialEq
+{
+    for size in [1, 2, 5, 20] {
+        for_all(solution, size, |parsed| {
+            let printed = print(parsed);
+            let reparsed = solution.parse(&printed)
+                .map_err(|error| format!("printed input doesn't parse: {}\n{}", error, printed))?;
+            check(&reparsed == parsed, || format!("printed as:\n{}\nparsed back as {:?}", printed, reparsed))?;
+            check(print(&reparsed) == printed, || format!("printed differently the second time:\n{}", printed))
+        });
+    }
+}
+
+#[test]
+fn test_round_trips() {
+    round_trips(&day1::Day1, |lines| lines.iter().map(|line| format!("{}\n", line)).collect());
+    round_trips(&day2::Day2, |games| lines(games));
+    round_trips(&day3::Day3, |schematic| schematic.to_string());
+    round_trips(&day4::Day4, |cards| lines(cards));
+    round_trips(&day5::Day5, |almanac| almanac.to_string());
+    round_trips(&day6::Day6, |sheet| sheet.to_string());
+    round_trips(&day7::Day7, |bids| bids.to_string());
+}
+
+#[test]
+fn test_day2_minimum_required() {
+    for_all(&day2::Day2, 20, |games| {
+        for game in games {
+            let minimum = game.minimum_required();
+
+            for set in &game.sets {
+                check(
+                    set.red <= minimum.red && set.green <= minimum.green && set.blue <= minimum.blue,
+                    || format!("game {}: {} needs more than {}", game.id, set, minimum)
+                )?;
+            }
+
+            let attained = [
+                |cubes: &day2::Cubes| cubes.red,
+                |cubes: &day2::Cubes| cubes.green,
+                |cubes: &day2::Cubes| cubes.blue
+            ];
+
+            for count in attained {
+                check(
+                    count(&minimum) == 0 || game.sets.iter().any(|set| count(set) == count(&minimum)),
+                    || format!("game {}: {} is more than any set needs", game.id, minimum)
+                )?;
+            }
+
+            check(
+                game.is_possible() == (minimum.red <= 12 && minimum.green <= 13 && minimum.blue <= 14),
+                || format!("game {}: is_possible disagrees with {}", game.id, minimum)
+            )?;
+        }
+
+        Ok(())
+    });
+}
+
+// Checks that `compare` is a total order over `hands`, in which only
+// identical hands are equal.
+fn total_order<T: Debug + PartialEq>(hands: &[T], compare: impl Fn(&T, &T) -> Ordering) -> Result<(), String> {
+    for a in hands {
+        for b in hands {
+            let ordering = compare(a, b);
+            check(ordering == compare(b, a).reverse(), || format!("{:?} and {:?} aren't antisymmetric", a, b))?;
+            check(
+                (ordering == Ordering::Equal) == (a == b),
+                || format!("{:?} and {:?} compare as {:?}", a, b, ordering)
+            )?;
+
+            for c in hands {
+                if ordering != Ordering::Greater && compare(b, c) != Ordering::Greater {
+                    check(
+                        compare(a, c) != Ordering::Greater,
+                        || format!("{:?} <= {:?} <= {:?} isn't transitive", a, b, c)
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_day7_compare_hands_is_total_order() {
+    for_all(&day7::Day7, 20, |bids| {
+        let hands: Vec<Vec<day7_part1::Card>> = bids.part1.iter()
+            .map(|bid| bid.cards.clone())
+            .collect();
+        total_order(&hands, |a, b| day7_part1::compare_hands(a, b))?;
+
+        let hands: Vec<Vec<day7_part2::FixedUpCard>> = bids.part2.iter()
+            .map(|bid| day7_part2::replace_jokers_in_hand(&bid.cards))
+            .collect();
+        total_order(&hands, |a, b| day7_part2::compare_hands(a, b))
+    });
+}
+
+#[test]
+fn test_day5_identity_outside_ranges() {
+    let mut rng = Rng::new(0);
+
+    for_all(&day5::Day5, 5, |almanac| {
+        let outside = |id: u64| almanac.maps.iter()
+            .flat_map(|map| &map.ranges)
+            .all(|range| !range.source().contains(id));
+
+        for _ in 0..100 {
+            let id = rng.below(10_000);
+
+            if outside(id) {
+                let location = day5::seed_location(id, almanac);
+                check(location == id, || format!("{} unmapped everywhere but located at {}", id, location))?;
+            }
+        }
+
+        Ok(())
+    });
+}